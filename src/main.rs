@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand, command};
+use clap::{Parser, Subcommand};
 use serde::Serialize;
 use thiserror::Error;
 
@@ -37,7 +37,7 @@ struct CliArgs {
 #[derive(Subcommand, Debug, Serialize, Clone)]
 #[serde(rename_all = "kebab-case")]
 enum Command {
-    Unreal(UnrealArgs),
+    Unreal(Box<UnrealArgs>),
     Clean(CleanArgs),
     Setup(PackagesArgs),
 }
@@ -47,10 +47,24 @@ async fn main() -> Result<(), Error> {
     let args = CliArgs::parse();
 
     match args.command {
-        Command::Unreal(args) => unreal_engine::command::process_unreal_command(args)?,
+        Command::Unreal(args) => {
+            unreal_engine::command::process_unreal_command(*args).map_err(exit_with_child_status)?
+        }
         Command::Clean(args) => cleaner::command::process_clean_command(args).await?,
         Command::Setup(args) => packages::command::setup(args).await?,
     };
 
     Ok(())
 }
+
+/* Exits with the exit code of a failed child process, so CI sees the editor's or UBT's own code. */
+fn exit_with_child_status(error: UnrealError) -> UnrealError {
+    if let UnrealError::FailedExitStatus { status } = &error
+        && let Some(code) = status.code()
+    {
+        eprintln!("Error: {}", Error::Unreal(error));
+        std::process::exit(code);
+    }
+
+    error
+}
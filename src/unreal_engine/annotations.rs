@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    unreal_engine::{
+        diagnostics::{Diagnostic, Severity},
+        error::UnrealError,
+    },
+    utility::{hash, repository},
+};
+
+#[derive(ValueEnum, Serialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnnotationFormat {
+    /// GitHub Actions workflow commands, printed to stdout.
+    Github,
+    /// a GitLab Code Quality report, written to the annotations path.
+    Gitlab,
+}
+
+/// An entry of a GitLab Code Quality report.
+#[derive(Serialize)]
+struct CodeQualityIssue {
+    description: String,
+    check_name: String,
+    fingerprint: String,
+    severity: &'static str,
+    location: CodeQualityLocation,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLines {
+    begin: u32,
+}
+
+/* Emits the diagnostics in the requested format, with file paths relative to the repository containing the project. */
+pub fn emit(
+    diagnostics: &[Diagnostic],
+    format: AnnotationFormat,
+    project_path: &Path,
+    report_path: &Path,
+) -> Result<(), UnrealError> {
+    let root = repository::repository_root(project_path);
+
+    let relative = |file: &str| match &root {
+        Some(root) => repository::relative_path(root, file),
+        None => file.replace('\\', "/"),
+    };
+
+    match format {
+        AnnotationFormat::Github => {
+            for diagnostic in diagnostics {
+                println!(
+                    "{}",
+                    github_command(diagnostic, diagnostic.file.as_deref().map(relative))
+                );
+            }
+
+            Ok(())
+        }
+        AnnotationFormat::Gitlab => {
+            let fallback = relative(&project_path.to_string_lossy());
+
+            let issues: Vec<_> = diagnostics
+                .iter()
+                .map(|diagnostic| {
+                    let path = diagnostic.file.as_deref().map(relative);
+                    code_quality_issue(diagnostic, path.unwrap_or_else(|| fallback.clone()))
+                })
+                .collect();
+
+            let file = std::fs::File::create(report_path)?;
+            serde_json::to_writer_pretty(file, &issues)?;
+
+            println!(
+                "Wrote {} issue(s) to {}.",
+                issues.len(),
+                report_path.display()
+            );
+            Ok(())
+        }
+    }
+}
+
+/* Formats `::error file=...,line=...,col=...,title=...::message`. */
+fn github_command(diagnostic: &Diagnostic, file: Option<String>) -> String {
+    let command = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+
+    let properties = [
+        file.map(|file| format!("file={}", escape_property(&file))),
+        diagnostic.line.map(|line| format!("line={line}")),
+        diagnostic.column.map(|column| format!("col={column}")),
+        diagnostic
+            .code
+            .as_ref()
+            .map(|code| format!("title={}", escape_property(code))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(",");
+
+    let message = escape_data(&diagnostic.message);
+
+    match properties.is_empty() {
+        true => format!("::{command}::{message}"),
+        false => format!("::{command} {properties}::{message}"),
+    }
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn code_quality_issue(diagnostic: &Diagnostic, path: String) -> CodeQualityIssue {
+    let severity = match diagnostic.severity {
+        Severity::Error => "major",
+        Severity::Warning => "minor",
+    };
+
+    let check_name = diagnostic
+        .code
+        .clone()
+        .unwrap_or_else(|| String::from("compiler"));
+
+    let line = diagnostic.line.unwrap_or(1);
+    let fingerprint = fingerprint(&[&path, &line.to_string(), &check_name, &diagnostic.message]);
+
+    CodeQualityIssue {
+        description: diagnostic.message.clone(),
+        check_name,
+        fingerprint,
+        severity,
+        location: CodeQualityLocation {
+            path,
+            lines: CodeQualityLines { begin: line },
+        },
+    }
+}
+
+/* Stable across runs and toolchains so GitLab can match issues between pipelines. */
+fn fingerprint(parts: &[&str]) -> String {
+    format!("{:016x}", hash::fnv1a(parts))
+}
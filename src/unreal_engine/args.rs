@@ -1,7 +1,22 @@
+use std::path::Path;
+
 use clap::Args;
 use serde::Serialize;
 
-use crate::{unreal_engine::command::UnrealCommand, utility::search::SearchOptions};
+use crate::{
+    unreal_engine::{
+        annotations::AnnotationFormat,
+        build_matrix::{self, BuildCell},
+        build_target::{BuildConfiguration, TargetSelector},
+        command::UnrealCommand,
+        error::UnrealError,
+        host_platform,
+        project_files::IdeFormat,
+        sharding::ShardSpec,
+        unreal_log::{LogFormat, Verbosity},
+    },
+    utility::search::SearchOptions,
+};
 
 #[derive(Debug, Args, Serialize, Clone)]
 #[command(version, about, long_about = None)]
@@ -12,3 +27,349 @@ pub struct UnrealArgs {
     #[command(flatten)]
     pub search_options: SearchOptions,
 }
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct BuildArgs {
+    #[arg(short = 'c', long = "config", value_enum, ignore_case = true, default_value_t = BuildConfiguration::Development)]
+    /// the build configuration.
+    pub configuration: BuildConfiguration,
+
+    #[arg(short = 't', long = "target-type", default_value = "Editor")]
+    /// the target to build, a target type (Editor, Game, Client, Server) or an explicit target name.
+    pub target: TargetSelector,
+
+    #[arg(long = "platform", default_value = host_platform::PLATFORM)]
+    /// the platform to build for, defaults to the host.
+    pub platform: String,
+
+    #[arg(
+        short = 'm',
+        long = "matrix",
+        value_delimiter = ',',
+        conflicts_with = "matrix_file"
+    )]
+    /// builds several combinations in order, Ex : "Editor:Development,Game:Shipping:Linux"
+    pub matrix: Vec<BuildCell>,
+
+    #[arg(long = "matrix-file")]
+    /// a toml file listing the matrix cells.
+    pub matrix_file: Option<String>,
+
+    #[arg(short = 'j', long = "jobs", default_value_t = 1)]
+    /// how many matrix cells are built at once, concurrent cells don't wait on UBT's mutex.
+    pub jobs: usize,
+
+    #[arg(short = 'k', long = "keep-going")]
+    /// keep building the remaining cells after a failure.
+    pub keep_going: bool,
+
+    #[arg(long = "diagnostics")]
+    /// writes the parsed compiler diagnostics to a json file.
+    pub diagnostics_path: Option<String>,
+
+    #[arg(long = "annotations", value_enum)]
+    /// emits the diagnostics as CI annotations.
+    pub annotations: Option<AnnotationFormat>,
+
+    #[arg(
+        long = "annotations-path",
+        default_value = "gl-code-quality-report.json"
+    )]
+    /// where the GitLab Code Quality report is written.
+    pub annotations_path: String,
+
+    #[arg(long = "warning-baseline")]
+    /// fails the build when it has warnings the baseline json file doesn't.
+    pub warning_baseline: Option<String>,
+
+    #[arg(long = "update-baseline", requires = "warning_baseline")]
+    /// rewrites the baseline when warnings were fixed, or creates it when missing.
+    pub update_baseline: bool,
+}
+
+impl BuildArgs {
+    /* The matrix cells, from --matrix or --matrix-file. Empty for a single build. */
+    pub fn matrix_cells(&self) -> Result<Vec<BuildCell>, UnrealError> {
+        match &self.matrix_file {
+            Some(path) => build_matrix::read_matrix_file(Path::new(path)),
+            None => Ok(self.matrix.clone()),
+        }
+    }
+
+    /* The single combination described by --target-type, --config and --platform. */
+    pub fn single_cell(&self) -> BuildCell {
+        BuildCell {
+            target: self.target.clone(),
+            configuration: self.configuration,
+            platform: Some(self.platform.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct TestArgs {
+    #[arg(short = 'f', long = "filter", default_value = "Project.")]
+    /// the automation tests to run, a test path prefix. Ex : "Project.Gameplay"
+    pub filter: String,
+
+    #[arg(long = "junit", default_value = "automation-junit.xml")]
+    /// where the JUnit report is written.
+    pub junit: String,
+
+    #[arg(long = "report-dir")]
+    /// the directory the editor exports its report to, defaults to Saved/Automation/CiReport.
+    pub report_dir: Option<String>,
+
+    #[arg(long = "shard")]
+    /// only runs one shard of the tests matching the filter, Ex : "2/4"
+    pub shard: Option<ShardSpec>,
+
+    #[arg(long = "test-list", requires = "shard")]
+    /// a file listing the tests one per line, written from the editor's `Automation List` when missing.
+    pub test_list: Option<String>,
+
+    #[arg(long = "timings", requires = "shard", value_delimiter = ',')]
+    /// previous JUnit reports, used to balance the shards by test duration.
+    pub timings: Vec<String>,
+
+    #[arg(long = "quarantine")]
+    /// the quarantine file, defaults to .ci_helper/quarantine.toml at the repository root.
+    pub quarantine: Option<String>,
+
+    #[arg(long = "retries", default_value_t = 0)]
+    /// how many times the failed tests are run again before they fail the job.
+    pub retries: u32,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct MergeReportsArgs {
+    #[arg(short = 'o', long = "output", default_value = "automation-junit.xml")]
+    /// where the merged JUnit report is written.
+    pub output: String,
+
+    #[arg(required = true)]
+    /// the JUnit reports of every shard.
+    pub reports: Vec<String>,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct PackageArgs {
+    #[arg(short = 'c', long = "config", value_enum, ignore_case = true, default_value_t = BuildConfiguration::Development)]
+    /// the client and server configuration.
+    pub configuration: BuildConfiguration,
+
+    #[arg(long = "platform", default_value = host_platform::PLATFORM)]
+    /// the platform to package for, defaults to the host.
+    pub platform: String,
+
+    #[arg(short = 't', long = "target")]
+    /// an explicit target to package, a target type or name, UAT picks the game target otherwise.
+    pub target: Option<TargetSelector>,
+
+    #[arg(long = "client")]
+    /// packages the client target instead of the game.
+    pub client: bool,
+
+    #[arg(long = "server")]
+    /// also packages the server target.
+    pub server: bool,
+
+    #[arg(long = "server-only", requires = "server")]
+    /// only packages the server, skipping the client or game.
+    pub server_only: bool,
+
+    #[arg(long = "server-platform", requires = "server")]
+    /// the platform of the server, defaults to the package platform.
+    pub server_platform: Option<String>,
+
+    #[arg(long = "maps", value_delimiter = ',')]
+    /// the maps to cook, all the maps in the project settings when omitted.
+    pub maps: Vec<String>,
+
+    #[arg(long = "pak")]
+    /// packs the content into pak files.
+    pub pak: bool,
+
+    #[arg(long = "iostore", requires = "pak")]
+    /// packs the content into IoStore containers.
+    pub iostore: bool,
+
+    #[arg(long = "compressed")]
+    /// compresses the packed content.
+    pub compressed: bool,
+
+    #[arg(long = "archive-dir")]
+    /// archives the packaged build to this directory.
+    pub archive_dir: Option<String>,
+
+    #[arg(long = "staging-dir")]
+    /// stages to this directory instead of Saved/StagedBuilds.
+    pub staging_dir: Option<String>,
+
+    #[arg(long = "no-debug-info")]
+    /// leaves the debug files out of the package.
+    pub no_debug_info: bool,
+
+    #[arg(long = "prereqs")]
+    /// includes the prerequisites installer.
+    pub prereqs: bool,
+
+    #[arg(long = "distribution")]
+    /// marks the build for distribution, signing it with the distribution certificates.
+    pub distribution: bool,
+
+    #[arg(long = "skip-build")]
+    /// packages the existing binaries instead of building them.
+    pub skip_build: bool,
+
+    #[arg(long = "skip-cook")]
+    /// stages the existing cooked content instead of cooking it.
+    pub skip_cook: bool,
+
+    #[arg(last = true)]
+    /// extra arguments passed to BuildCookRun as is, after `--`.
+    pub extra: Vec<String>,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct GenerateArgs {
+    #[arg(long = "format", value_enum, conflicts_with = "clang_database")]
+    /// the IDE to generate project files for, UBT picks the host's default when omitted.
+    pub format: Option<IdeFormat>,
+
+    #[arg(long = "clang-database")]
+    /// generates compile_commands.json instead of project files.
+    pub clang_database: bool,
+
+    #[arg(
+        short = 't',
+        long = "target-type",
+        default_value = "Editor",
+        requires = "clang_database"
+    )]
+    /// the target the compile commands are generated for.
+    pub target: TargetSelector,
+
+    #[arg(short = 'c', long = "config", value_enum, ignore_case = true, default_value_t = BuildConfiguration::Development, requires = "clang_database")]
+    /// the configuration the compile commands are generated for.
+    pub configuration: BuildConfiguration,
+
+    #[arg(long = "platform", default_value = host_platform::PLATFORM, requires = "clang_database")]
+    /// the platform the compile commands are generated for, defaults to the host.
+    pub platform: String,
+
+    #[arg(short = 'o', long = "output-dir", requires = "clang_database")]
+    /// where compile_commands.json is written, defaults to the project directory.
+    pub output_dir: Option<String>,
+
+    #[arg(long = "relative", requires = "clang_database")]
+    /// makes the paths into the repository relative, so agents can share the file. Clang tools are then run from the repository root.
+    pub relative: bool,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct CommandletArgs {
+    /// the commandlet to run, Ex : "ResavePackages"
+    pub name: String,
+
+    #[arg(long = "json")]
+    /// writes the result and its summary lines to a json file.
+    pub json: Option<String>,
+
+    #[arg(last = true)]
+    /// extra arguments passed to the commandlet as is, after `--`.
+    pub extra: Vec<String>,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct PythonArgs {
+    #[arg(long = "log")]
+    /// writes the LogPython output to a file.
+    pub log: Option<String>,
+
+    /// the Python script to run inside the editor.
+    pub script: String,
+
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    /// the script's arguments, available in sys.argv.
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct ValidateAssetsArgs {
+    #[arg(long = "paths", value_delimiter = ',')]
+    /// only reports the assets under these package paths, Ex : "/Game/Maps"
+    pub paths: Vec<String>,
+
+    #[arg(long = "changed")]
+    /// only reports the assets changed in the current git diff.
+    pub changed: bool,
+
+    #[arg(long = "base", default_value = "HEAD", requires = "changed")]
+    /// the revision the diff starts from, the merge base with HEAD is used. Ex : "origin/main"
+    pub base: String,
+
+    #[arg(long = "json")]
+    /// writes the issues to a json file.
+    pub json: Option<String>,
+
+    #[arg(long = "junit")]
+    /// writes the issues as a JUnit report, one test case per asset.
+    pub junit: Option<String>,
+
+    #[arg(last = true)]
+    /// extra arguments passed to the commandlet as is, after `--`.
+    pub extra: Vec<String>,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct LogsArgs {
+    /// the log file to read, Ex : "Saved/Logs/MyGame.log"
+    pub file: String,
+
+    #[arg(long = "category", value_delimiter = ',')]
+    /// only keeps these categories, Ex : "LogPython,LogBlueprint"
+    pub categories: Vec<String>,
+
+    #[arg(long = "verbosity", value_enum, ignore_case = true)]
+    /// the least severe verbosity kept, Ex : "Warning" keeps fatal errors, errors and warnings.
+    pub verbosity: Option<Verbosity>,
+
+    #[arg(long = "counts")]
+    /// prints how many messages each category logged instead of the lines.
+    pub counts: bool,
+
+    #[arg(long = "format", value_enum, default_value_t = LogFormat::Text)]
+    pub format: LogFormat,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct RunArgs {
+    #[arg(long = "follow")]
+    /// prints the project's log while the editor runs, then exits with the editor's exit status.
+    pub follow: bool,
+
+    #[arg(long = "category", value_delimiter = ',', requires = "follow")]
+    /// only prints these categories, Ex : "LogPython,LogBlueprint"
+    pub categories: Vec<String>,
+
+    #[arg(
+        long = "verbosity",
+        value_enum,
+        ignore_case = true,
+        requires = "follow"
+    )]
+    /// the least severe verbosity printed, Ex : "warning" prints fatal errors, errors and warnings.
+    pub verbosity: Option<Verbosity>,
+
+    #[arg(long = "no-color", requires = "follow")]
+    /// doesn't highlight the errors and warnings, also disabled by the NO_COLOR environment variable.
+    pub no_color: bool,
+}
+
+#[derive(Debug, Args, Serialize, Clone)]
+pub struct InfoArgs {
+    #[arg(long)]
+    /// print the descriptor and the engine as json.
+    pub json: bool,
+}
@@ -0,0 +1,330 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    unreal_engine::{
+        args::ValidateAssetsArgs, commandlet, diagnostics::Severity, error::UnrealError,
+        unreal_project::UnrealProject,
+    },
+    utility::{
+        junit::{TestCase, TestOutcome, TestSuite},
+        path_utility, repository,
+    },
+};
+
+const ASSET_EXTENSIONS: &[&str] = &["uasset", "umap"];
+
+/// An error or warning the DataValidation commandlet reported for one asset.
+#[derive(Serialize, PartialEq, Eq, Hash, Debug, Clone)]
+pub struct AssetIssue {
+    /// the package name, Ex : "/Game/Maps/Arena"
+    pub asset: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/* Runs the DataValidation commandlet and reports the issues of the requested assets. */
+pub fn run(project: &UnrealProject, args: &ValidateAssetsArgs) -> Result<(), UnrealError> {
+    let result = commandlet::run(project, "DataValidation", &args.extra)?;
+
+    let project_dir = project.project_dir();
+    let mut issues = parse_issues(&result.errors, &result.warnings, project_dir);
+
+    // A failure without any asset issue is the commandlet itself failing.
+    if issues.is_empty() && !result.succeeded() {
+        return Err(UnrealError::CommandletFailed {
+            errors: result.errors.len(),
+            code: result.exit_code,
+            name: result.name,
+        });
+    }
+
+    // The commandlet validates the whole project, the paths only narrow the report.
+    if !args.paths.is_empty() {
+        issues.retain(|issue| {
+            args.paths
+                .iter()
+                .any(|path| is_under_package_path(&issue.asset, path))
+        });
+    }
+
+    if args.changed {
+        let changed = changed_assets(project_dir, &args.base)?;
+        println!("\n{} changed asset(s) since {}.", changed.len(), args.base);
+
+        issues.retain(|issue| changed.contains(&issue.asset));
+    }
+
+    if let Some(path) = &args.json {
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(file, &issues)?;
+    }
+
+    if let Some(path) = &args.junit {
+        to_junit(&issues).write(Path::new(path))?;
+    }
+
+    for issue in &issues {
+        println!("{:?} : {} : {}", issue.severity, issue.asset, issue.message);
+    }
+
+    let failing: HashSet<&str> = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .map(|issue| issue.asset.as_str())
+        .collect();
+
+    println!(
+        "\nAsset Validation : {} issue(s), {} asset(s) with errors.",
+        issues.len(),
+        failing.len()
+    );
+
+    match failing.len() {
+        0 => Ok(()),
+        assets => Err(UnrealError::AssetValidationFailed { assets }),
+    }
+}
+
+/* Keeps the summary lines naming an asset, the others are about the commandlet itself. */
+fn parse_issues(errors: &[String], warnings: &[String], project_dir: &Path) -> Vec<AssetIssue> {
+    let lines = errors
+        .iter()
+        .map(|line| (line, Severity::Error))
+        .chain(warnings.iter().map(|line| (line, Severity::Warning)));
+
+    let mut seen = HashSet::new();
+
+    lines
+        .filter_map(|(line, severity)| parse_issue(line, severity, project_dir))
+        .filter(|issue| seen.insert(issue.clone()))
+        .collect()
+}
+
+/* Parses `LogContentValidation: Error: /Game/Maps/Arena.Arena: message`, the asset can also be named by its file. */
+fn parse_issue(line: &str, severity: Severity, project_dir: &Path) -> Option<AssetIssue> {
+    let marker = match severity {
+        Severity::Error => ": Error: ",
+        Severity::Warning => ": Warning: ",
+    };
+
+    let (_, message) = line.split_once(marker)?;
+
+    let asset = message.split_whitespace().find_map(|token| {
+        let token = token
+            .trim_start_matches(['(', '\'', '"'])
+            .trim_end_matches([':', ',', '.', ')', '\'', '"']);
+
+        package_name(token, project_dir)
+    })?;
+
+    Some(AssetIssue {
+        asset,
+        severity,
+        message: message.trim().to_owned(),
+    })
+}
+
+/* `/Game/Maps/Arena.Arena` and `.../Content/Maps/Arena.umap` are both `/Game/Maps/Arena`. */
+fn package_name(token: &str, project_dir: &Path) -> Option<String> {
+    let token = token.replace('\\', "/");
+
+    let is_file = Path::new(&token)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| ASSET_EXTENSIONS.contains(&extension));
+
+    if is_file {
+        return package_from_file(Path::new(&token), project_dir);
+    }
+
+    if !token.starts_with('/') || token[1..].find('/').is_none() {
+        return None;
+    }
+
+    // Drops the object name after the last segment's dot.
+    let (package_path, name) = token.rsplit_once('/')?;
+    let name = name.split_once('.').map_or(name, |(package, _)| package);
+
+    Some(format!("{package_path}/{name}"))
+}
+
+/* Maps a file under a Content directory to its package, the project's content is mounted as /Game
+and a plugin's under its name. */
+fn package_from_file(path: &Path, project_dir: &Path) -> Option<String> {
+    let components: Vec<String> = path
+        .with_extension("")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    let content = components
+        .iter()
+        .rposition(|component| component == "Content")?;
+
+    let project_name = project_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+
+    let root = match content.checked_sub(1).map(|index| &components[index]) {
+        Some(parent) if Some(parent) != project_name.as_ref() && parent != ".." => parent.as_str(),
+        _ => "Game",
+    };
+
+    Some(format!("/{root}/{}", components[content + 1..].join("/")))
+}
+
+fn is_under_package_path(asset: &str, path: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    asset == path || asset.starts_with(&format!("{path}/"))
+}
+
+/* The packages of the assets changed since the merge base with the base revision. */
+fn changed_assets(project_dir: &Path, base: &str) -> Result<HashSet<String>, UnrealError> {
+    let files = repository::changed_files(project_dir, base)?;
+    let project_dir = path_utility::strip_verbatim(
+        project_dir
+            .canonicalize()
+            .unwrap_or_else(|_| project_dir.to_path_buf()),
+    );
+
+    Ok(files
+        .iter()
+        .filter_map(|file| {
+            let relative = file.strip_prefix(&project_dir).ok()?;
+            let extension = relative.extension()?.to_str()?;
+
+            ASSET_EXTENSIONS
+                .contains(&extension)
+                .then(|| package_from_file(relative, &project_dir))?
+        })
+        .collect())
+}
+
+/* One test case per asset, failed when it has errors and passed with its warnings as output otherwise. */
+fn to_junit(issues: &[AssetIssue]) -> TestSuite {
+    let mut assets: BTreeMap<&str, Vec<&AssetIssue>> = BTreeMap::new();
+
+    for issue in issues {
+        assets.entry(&issue.asset).or_default().push(issue);
+    }
+
+    let cases = assets
+        .into_iter()
+        .map(|(asset, issues)| {
+            let messages = |severity: Severity| -> Vec<String> {
+                issues
+                    .iter()
+                    .filter(|issue| issue.severity == severity)
+                    .map(|issue| issue.message.clone())
+                    .collect()
+            };
+
+            let errors = messages(Severity::Error);
+            let outcome = match errors.is_empty() {
+                true => TestOutcome::Passed,
+                false => TestOutcome::Failed { errors },
+            };
+
+            let path = asset.trim_start_matches('/').replace('/', ".");
+
+            let mut case = TestCase::from_path(&path, 0.0, outcome);
+            case.output = messages(Severity::Warning);
+            case
+        })
+        .collect();
+
+    TestSuite {
+        name: String::from("AssetValidation"),
+        cases,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_tokens_to_packages() {
+        let project_dir = Path::new("/work/Demo");
+
+        let cases = [
+            ("/Game/Maps/Arena.Arena", Some("/Game/Maps/Arena")),
+            ("/Game/Maps/Arena", Some("/Game/Maps/Arena")),
+            ("/Tools/Icons/Gear.Gear_C", Some("/Tools/Icons/Gear")),
+            (
+                "/work/Demo/Content/Maps/Arena.umap",
+                Some("/Game/Maps/Arena"),
+            ),
+            (
+                r"C:\work\Demo\Content\UI\Menu.uasset",
+                Some("/Game/UI/Menu"),
+            ),
+            ("Content/UI/Menu.uasset", Some("/Game/UI/Menu")),
+            ("../../Content/UI/Menu.uasset", Some("/Game/UI/Menu")),
+            (
+                "/work/Demo/Plugins/Tools/Content/Icons/Gear.uasset",
+                Some("/Tools/Icons/Gear"),
+            ),
+            ("/work/Demo/Source/Gear.uasset", None),
+            ("/Game", None),
+            ("Arena.Arena", None),
+            ("failed", None),
+        ];
+
+        for (token, expected) in cases {
+            assert_eq!(
+                package_name(token, project_dir).as_deref(),
+                expected,
+                "{token}"
+            );
+        }
+    }
+
+    #[test]
+    fn names_the_asset_of_an_issue() {
+        let project_dir = Path::new("/work/Demo");
+
+        let cases = [
+            (
+                "LogContentValidation: Error: /Game/Maps/Arena.Arena: missing navmesh",
+                Severity::Error,
+                Some("/Game/Maps/Arena"),
+            ),
+            (
+                "LogContentValidation: Warning: Texture '/Game/UI/Icon.Icon' is not a power of two.",
+                Severity::Warning,
+                Some("/Game/UI/Icon"),
+            ),
+            (
+                "LogContentValidation: Error: (/work/Demo/Content/Maps/Arena.umap) failed",
+                Severity::Error,
+                Some("/Game/Maps/Arena"),
+            ),
+            (
+                "LogContentValidation: Warning: /Game/Maps/Arena.Arena: unused",
+                Severity::Error,
+                None,
+            ),
+            (
+                "LogContentValidation: Error: validation failed",
+                Severity::Error,
+                None,
+            ),
+        ];
+
+        for (line, severity, expected) in cases {
+            let issue = parse_issue(line, severity, project_dir);
+
+            assert_eq!(
+                issue.map(|issue| issue.asset).as_deref(),
+                expected,
+                "{line}"
+            );
+        }
+    }
+}
@@ -0,0 +1,624 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Mutex,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    unreal_engine::{
+        args::{MergeReportsArgs, TestArgs},
+        error::UnrealError,
+        host_platform, process,
+        quarantine::Quarantine,
+        sharding,
+        unreal_log::{LogRecord, Verbosity},
+        unreal_project::UnrealProject,
+    },
+    utility::junit::{self, TestCase, TestOutcome, TestSuite},
+};
+
+/// The longest filter passed to one `Automation RunTests`, keeps the command line under Windows' 32767 characters.
+const MAX_FILTER_LENGTH: usize = 16_000;
+
+/// The `index.json` written to the `-ReportExportPath` directory.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationReport {
+    #[serde(default)]
+    pub tests: Vec<AutomationTest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationTest {
+    pub full_test_path: String,
+    pub state: TestState,
+    #[serde(default)]
+    pub duration: f64,
+    #[serde(default)]
+    pub entries: Vec<AutomationEntry>,
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy)]
+pub enum TestState {
+    Success,
+    Fail,
+    NotRun,
+    Skipped,
+    InProcess,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationEntry {
+    pub event: AutomationEvent,
+    #[serde(default)]
+    pub filename: String,
+    #[serde(default)]
+    pub line_number: i64,
+}
+
+#[derive(Deserialize)]
+pub struct AutomationEvent {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+impl AutomationReport {
+    pub fn from_file(path: &Path) -> Result<AutomationReport, UnrealError> {
+        let contents = std::fs::read(path)?;
+
+        // The editor writes the report with a UTF-8 BOM.
+        let contents = contents.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&contents);
+
+        Ok(serde_json::from_slice(contents)?)
+    }
+
+    pub fn to_junit(&self) -> TestSuite {
+        TestSuite {
+            name: String::from("UnrealAutomation"),
+            cases: self.tests.iter().map(AutomationTest::to_junit).collect(),
+        }
+    }
+}
+
+impl AutomationTest {
+    fn messages(&self, kind: &str) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.event.kind.eq_ignore_ascii_case(kind))
+            .map(|entry| match entry.filename.is_empty() {
+                true => entry.event.message.clone(),
+                false => format!(
+                    "{} ({}:{})",
+                    entry.event.message, entry.filename, entry.line_number
+                ),
+            })
+            .collect()
+    }
+
+    fn to_junit(&self) -> TestCase {
+        let outcome = match self.state {
+            TestState::Success => TestOutcome::Passed,
+            TestState::Fail => TestOutcome::Failed {
+                errors: self.messages("Error"),
+            },
+            TestState::NotRun | TestState::Skipped => TestOutcome::Skipped {
+                reason: String::from("not run"),
+            },
+            // A test still in process when the report was written took the editor down with it.
+            TestState::InProcess | TestState::Unknown => TestOutcome::Failed {
+                errors: vec![String::from("the test did not finish")],
+            },
+        };
+
+        let mut case = TestCase::from_path(&self.full_test_path, self.duration, outcome);
+        case.output = self.messages("Warning");
+        case
+    }
+}
+
+/* Runs the automation tests matching the filter headlessly, then exports the report as JUnit. */
+pub fn run(project: &UnrealProject, args: &TestArgs) -> Result<(), UnrealError> {
+    let selected = match args.shard {
+        Some(shard) => {
+            let tests = list_tests(project, args)?;
+            let timings = sharding::read_timings(&args.timings);
+            let selected = sharding::select(&tests, shard, &timings);
+
+            println!(
+                "\nShard {shard} : {} of {} tests.",
+                selected.len(),
+                tests.len()
+            );
+
+            // More shards than tests leaves some of them empty, which isn't a failure.
+            if selected.is_empty() {
+                let suite = TestSuite {
+                    name: String::from("UnrealAutomation"),
+                    cases: Vec::new(),
+                };
+
+                return Ok(suite.write(Path::new(&args.junit))?);
+            }
+
+            Some((shard, selected))
+        }
+        None => None,
+    };
+
+    let report_dir = match &args.report_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => project
+            .project_dir()
+            .join("Saved")
+            .join("Automation")
+            .join("CiReport"),
+    };
+
+    let quarantine = Quarantine::load(project, args.quarantine.as_deref())?;
+
+    let mut suite = match &selected {
+        Some((shard, tests)) => {
+            println!(
+                "\nRunning Automation Tests : shard {shard}, {} tests..",
+                tests.len()
+            );
+            run_selected(project, tests, &report_dir)?
+        }
+        None => {
+            println!("\nRunning Automation Tests : {}..", args.filter);
+            run_tests(project, &args.filter, &report_dir)?
+        }
+    };
+
+    for attempt in 1..=args.retries {
+        // Quarantined tests aren't retried, their failures are skipped anyway.
+        let failed: Vec<String> = suite
+            .cases
+            .iter()
+            .filter(|case| matches!(case.outcome, TestOutcome::Failed { .. }))
+            .map(TestCase::full_name)
+            .filter(|test| quarantine.find(test).is_none())
+            .collect();
+
+        if failed.is_empty() {
+            break;
+        }
+
+        println!(
+            "\nRetrying {} failed test(s), attempt {attempt} of {}..",
+            failed.len(),
+            args.retries
+        );
+
+        let retried = match run_selected(project, &failed, &report_dir) {
+            Ok(retried) => retried,
+            Err(err) => {
+                eprintln!("{err}");
+                break;
+            }
+        };
+
+        merge_retry(&mut suite, retried, attempt);
+    }
+
+    // Tests that failed every attempt aren't flaky, their last failure is reported as is.
+    for case in &mut suite.cases {
+        if matches!(case.outcome, TestOutcome::Failed { .. }) {
+            case.flaky_failures.clear();
+        }
+    }
+
+    let quarantined = quarantine.apply(&mut suite);
+    suite.write(Path::new(&args.junit))?;
+
+    let flaky: Vec<&TestCase> = suite
+        .cases
+        .iter()
+        .filter(|case| !case.flaky_failures.is_empty())
+        .collect();
+
+    for case in &flaky {
+        println!(
+            "{} passed only on retry, consider quarantining it.",
+            case.full_name()
+        );
+    }
+
+    let failures = suite.failures();
+
+    println!(
+        "\nTests : {} passed ({} on retry), {} failed, {} skipped ({} quarantined). JUnit report : {}",
+        suite.cases.len() - failures - suite.skipped(),
+        flaky.len(),
+        failures,
+        suite.skipped(),
+        quarantined,
+        args.junit
+    );
+
+    match failures {
+        0 => Ok(()),
+        failed => Err(UnrealError::TestsFailed {
+            failed,
+            total: suite.cases.len(),
+        }),
+    }
+}
+
+/* Runs the tests matching the filter and reads the report the editor exported. */
+fn run_tests(
+    project: &UnrealProject,
+    filter: &str,
+    report_dir: &Path,
+) -> Result<TestSuite, UnrealError> {
+    let index_path = report_dir.join("index.json");
+
+    // A stale report from an earlier run would hide a crash of this one.
+    if index_path.exists() {
+        std::fs::remove_file(&index_path)?;
+    }
+
+    let mut command = editor_command(project);
+    exec_cmds(&mut command, &format!("Automation RunTests {filter};Quit"));
+    command.arg(format!(
+        "-ReportExportPath={}",
+        report_dir.to_string_lossy()
+    ));
+
+    let editor_result = process::run_to_completion(command, None, None);
+
+    if !index_path.exists() {
+        editor_result?;
+
+        return Err(UnrealError::ReportNotFound {
+            path: index_path.to_string_lossy().to_string(),
+        });
+    }
+
+    Ok(AutomationReport::from_file(&index_path)?.to_junit())
+}
+
+/* Runs exactly the named tests. RunTests matches its filters as substrings, so each name is anchored with `^...$`.
+A selected test missing from the reports fails, so a shard whose tests were renamed or never ran can't pass.
+A long list is run in batches. */
+fn run_selected(
+    project: &UnrealProject,
+    tests: &[String],
+    report_dir: &Path,
+) -> Result<TestSuite, UnrealError> {
+    let mut cases = Vec::new();
+
+    for filter in exact_filters(tests) {
+        cases.extend(run_tests(project, &filter, report_dir)?.cases);
+    }
+
+    let selected: HashSet<&str> = tests.iter().map(String::as_str).collect();
+    let mut seen = HashSet::new();
+
+    cases.retain(|case| {
+        let name = case.full_name();
+        selected.contains(name.as_str()) && seen.insert(name)
+    });
+
+    for test in tests {
+        if !seen.contains(test) {
+            cases.push(TestCase::from_path(
+                test,
+                0.0,
+                TestOutcome::Failed {
+                    errors: vec![String::from(
+                        "The test is missing from the editor's report, it didn't run.",
+                    )],
+                },
+            ));
+        }
+    }
+
+    Ok(TestSuite {
+        name: String::from("UnrealAutomation"),
+        cases,
+    })
+}
+
+/* Joins the anchored names into `+` separated filters, each short enough for Windows' command line. */
+fn exact_filters(tests: &[String]) -> Vec<String> {
+    let mut filters = Vec::new();
+    let mut filter = String::new();
+
+    for test in tests {
+        let anchored = format!("^{test}$");
+
+        if !filter.is_empty() && filter.len() + 1 + anchored.len() > MAX_FILTER_LENGTH {
+            filters.push(std::mem::take(&mut filter));
+        }
+
+        if !filter.is_empty() {
+            filter.push('+');
+        }
+
+        filter.push_str(&anchored);
+    }
+
+    if !filter.is_empty() {
+        filters.push(filter);
+    }
+
+    filters
+}
+
+/* Replaces the failed cases with their retried outcome, keeping the errors of the earlier attempts. */
+fn merge_retry(suite: &mut TestSuite, retried: TestSuite, attempt: u32) {
+    for retry in retried.cases {
+        let name = retry.full_name();
+
+        let Some(case) = suite.cases.iter_mut().find(|case| case.full_name() == name) else {
+            continue;
+        };
+
+        let TestOutcome::Failed { errors } = &case.outcome else {
+            continue;
+        };
+
+        case.flaky_failures.push(errors.join("\n"));
+
+        if retry.outcome == TestOutcome::Passed {
+            case.output.push(format!("passed on retry {attempt}"));
+        }
+
+        case.outcome = retry.outcome;
+        case.duration += retry.duration;
+    }
+}
+
+/* The headless editor, without the commands to execute. */
+fn editor_command(project: &UnrealProject) -> Command {
+    let exe_path = host_platform::editor_cmd_executable(Path::new(&project.engine().exe_path));
+
+    let mut command = Command::new(exe_path);
+    command.arg(project.path()).args([
+        "-unattended",
+        "-nullrhi",
+        "-nopause",
+        "-nosplash",
+        "-nosound",
+        "-stdout",
+        "-FullStdOutLogOutput",
+    ]);
+
+    command
+}
+
+/* Passes the console commands the editor executes once loaded. */
+#[cfg(not(target_os = "windows"))]
+fn exec_cmds(command: &mut Command, cmds: &str) {
+    command.arg(format!("-ExecCmds={cmds}"));
+}
+
+/* The editor parses its own command line on Windows and only reads a value with spaces when the value alone is quoted,
+`-ExecCmds="..."`. Rust would quote the whole argument instead, so it's passed raw. */
+#[cfg(target_os = "windows")]
+fn exec_cmds(command: &mut Command, cmds: &str) {
+    use std::os::windows::process::CommandExt;
+
+    command.raw_arg(format!("-ExecCmds=\"{cmds}\""));
+}
+
+/* The tests matching the filter, read from the test list or listed by the editor, which then writes the list. */
+fn list_tests(project: &UnrealProject, args: &TestArgs) -> Result<Vec<String>, UnrealError> {
+    let cached = args.test_list.as_ref().map(Path::new);
+
+    let tests: Vec<String> = match cached.filter(|path| path.exists()) {
+        Some(path) => std::fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_owned)
+            .collect(),
+        None => {
+            let listed = Mutex::new(Vec::new());
+
+            let observer = |line: &str| {
+                if let (Some(test), Ok(mut listed)) = (parse_listed_test(line), listed.lock()) {
+                    listed.push(test);
+                }
+            };
+
+            let mut command = editor_command(project);
+            exec_cmds(&mut command, "Automation List;Quit");
+
+            println!("\nListing Automation Tests..");
+            process::run_to_completion(command, None, Some(&observer))?;
+
+            let listed = listed.into_inner().unwrap_or_default();
+
+            if let Some(path) = cached {
+                std::fs::write(path, listed.join("\n") + "\n")?;
+            }
+
+            listed
+        }
+    };
+
+    let tests: Vec<String> = tests
+        .into_iter()
+        .filter(|test| test.starts_with(&args.filter))
+        .collect();
+
+    match tests.is_empty() {
+        true => Err(UnrealError::NoTestsFound {
+            filter: args.filter.clone(),
+        }),
+        false => Ok(tests),
+    }
+}
+
+/* `Automation List` prints each test on its own tab indented line, Ex :
+`LogAutomationCommandLine: Display: \tProject.Gameplay.Jump` */
+fn parse_listed_test(line: &str) -> Option<String> {
+    let record = LogRecord::parse(line);
+
+    if !record.is_category("LogAutomationCommandLine") || record.verbosity != Verbosity::Display {
+        return None;
+    }
+
+    let test = record.message.strip_prefix('\t')?.trim();
+    (!test.is_empty()).then(|| test.to_owned())
+}
+
+/* Combines the JUnit reports of every shard into one. A test reported by several shards is kept once, failed if
+any of them failed it. */
+pub fn merge_reports(args: &MergeReportsArgs) -> Result<(), UnrealError> {
+    let mut cases: Vec<TestCase> = Vec::new();
+    let mut indices = HashMap::new();
+    let mut duplicates = 0;
+
+    for report in &args.reports {
+        for case in junit::read_cases(Path::new(report))? {
+            let Some(&index) = indices.get(&case.full_name()) else {
+                indices.insert(case.full_name(), cases.len());
+                cases.push(case);
+                continue;
+            };
+
+            duplicates += 1;
+
+            let failed = |case: &TestCase| matches!(case.outcome, TestOutcome::Failed { .. });
+
+            if failed(&case) && !failed(&cases[index]) {
+                cases[index] = case;
+            }
+        }
+    }
+
+    let suite = TestSuite {
+        name: String::from("UnrealAutomation"),
+        cases,
+    };
+
+    suite.write(Path::new(&args.output))?;
+
+    println!(
+        "Merged {} report(s) : {} tests, {} failed, {} skipped, {} duplicate(s) dropped. JUnit report : {}",
+        args.reports.len(),
+        suite.cases.len(),
+        suite.failures(),
+        suite.skipped(),
+        duplicates,
+        args.output
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_listed_tests() {
+        let cases = [
+            (
+                "LogAutomationCommandLine: Display: \tProject.Gameplay.Jump",
+                Some("Project.Gameplay.Jump"),
+            ),
+            (
+                "[2024.01.01-12.00.00:000][  0]LogAutomationCommandLine: Display: \tProject.A ",
+                Some("Project.A"),
+            ),
+            ("LogAutomationCommandLine: Display: Found 2 tests", None),
+            ("LogAutomationCommandLine: Display: \t", None),
+            ("LogAutomationCommandLine: Warning: \tProject.A", None),
+            ("LogInit: Display: \tProject.A", None),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(parse_listed_test(line).as_deref(), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn anchors_and_splits_exact_filters() {
+        let tests = [String::from("Project.A"), String::from("Project.B")];
+
+        assert_eq!(exact_filters(&tests), ["^Project.A$+^Project.B$"]);
+        assert!(exact_filters(&[]).is_empty());
+
+        let long = "Project.".to_owned() + &"A".repeat(MAX_FILTER_LENGTH / 2);
+        let tests = [long.clone(), long.clone(), long];
+        let filters = exact_filters(&tests);
+
+        assert_eq!(filters.len(), 3);
+        assert!(
+            filters
+                .iter()
+                .all(|filter| filter.len() <= MAX_FILTER_LENGTH)
+        );
+    }
+
+    #[test]
+    fn merged_reports_keep_each_test_once_failed_if_any_shard_failed_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let failed = TestOutcome::Failed {
+            errors: vec![String::from("failed on the second shard")],
+        };
+
+        let shards = [
+            vec![
+                TestCase::from_path("Project.A", 1.0, TestOutcome::Passed),
+                TestCase::from_path("Project.B", 1.0, TestOutcome::Passed),
+            ],
+            vec![
+                TestCase::from_path("Project.B", 1.0, failed.clone()),
+                TestCase::from_path("Project.C", 1.0, TestOutcome::Passed),
+            ],
+            vec![TestCase::from_path("Project.B", 1.0, TestOutcome::Passed)],
+        ];
+
+        let reports = shards
+            .into_iter()
+            .enumerate()
+            .map(|(index, cases)| {
+                let path = dir.path().join(format!("shard-{index}.xml"));
+                let suite = TestSuite {
+                    name: String::from("UnrealAutomation"),
+                    cases,
+                };
+
+                suite.write(&path).unwrap();
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
+
+        let output = dir.path().join("merged.xml");
+
+        merge_reports(&MergeReportsArgs {
+            output: output.to_string_lossy().into_owned(),
+            reports,
+        })
+        .unwrap();
+
+        let merged: Vec<(String, TestOutcome)> = junit::read_cases(&output)
+            .unwrap()
+            .into_iter()
+            .map(|case| (case.full_name(), case.outcome))
+            .collect();
+
+        assert_eq!(
+            merged,
+            [
+                (String::from("Project.A"), TestOutcome::Passed),
+                (String::from("Project.B"), failed),
+                (String::from("Project.C"), TestOutcome::Passed),
+            ]
+        );
+    }
+}
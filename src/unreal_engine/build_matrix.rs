@@ -0,0 +1,278 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    str::FromStr,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::unreal_engine::{
+    args::BuildArgs,
+    build_target::{BuildConfiguration, TargetSelector},
+    diagnostics::DiagnosticCollector,
+    error::UnrealError,
+    unreal_project::UnrealProject,
+};
+
+/// One target, configuration and platform combination of a build matrix.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct BuildCell {
+    pub target: TargetSelector,
+    pub configuration: BuildConfiguration,
+    /// the platform to build for, the `--platform` argument when omitted.
+    #[serde(default)]
+    pub platform: Option<String>,
+}
+
+/// A matrix file, Ex :
+/// ```toml
+/// [[cells]]
+/// target = "Server"
+/// configuration = "Shipping"
+/// ```
+#[derive(Deserialize)]
+struct MatrixFile {
+    cells: Vec<BuildCell>,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum CellStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+struct CellResult {
+    status: CellStatus,
+    duration: Duration,
+}
+
+impl BuildCell {
+    pub fn platform<'a>(&'a self, default: &'a str) -> &'a str {
+        self.platform.as_deref().unwrap_or(default)
+    }
+}
+
+/* Reads the cells of a TOML matrix file. */
+pub fn read_matrix_file(path: &Path) -> Result<Vec<BuildCell>, UnrealError> {
+    let contents = std::fs::read_to_string(path)?;
+
+    let file: MatrixFile = toml::from_str(&contents).map_err(|err| UnrealError::InvalidMatrix {
+        reason: err.to_string(),
+    })?;
+
+    Ok(file.cells)
+}
+
+/* Builds every cell of the matrix, then prints a summary table. Fails if any cell failed. */
+pub fn run(
+    project: &UnrealProject,
+    args: &BuildArgs,
+    cells: &[BuildCell],
+    collector: &DiagnosticCollector,
+) -> Result<(), UnrealError> {
+    // Every target is checked up front, so a typo in the last cell doesn't surface hours in.
+    let targets = cells
+        .iter()
+        .map(|cell| {
+            project
+                .resolve_target(&cell.target)
+                .map(|target| target.name)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The cells of a target and platform share `Intermediate/Build/<Platform>/<Target>` whatever their configuration,
+    // so they are built one at a time even when the others run concurrently.
+    let mut shared: HashMap<(&str, &str), Mutex<()>> = HashMap::new();
+
+    for (cell, target) in cells.iter().zip(&targets) {
+        shared
+            .entry((target.as_str(), cell.platform(&args.platform)))
+            .or_default();
+    }
+
+    let jobs = args.jobs.max(1);
+    let results: Vec<Mutex<Option<CellResult>>> = cells.iter().map(|_| Mutex::new(None)).collect();
+
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+
+    std::thread::scope(|s| {
+        for _ in 0..jobs.min(cells.len()) {
+            s.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+
+                    let Some(cell) = cells.get(index) else {
+                        break;
+                    };
+
+                    if failed.load(Ordering::Relaxed) && !args.keep_going {
+                        break;
+                    }
+
+                    let label = format!(
+                        "{} {} {}",
+                        cell.target,
+                        cell.configuration.as_str(),
+                        cell.platform(&args.platform)
+                    );
+
+                    let label = (jobs > 1).then_some(label.as_str());
+
+                    let _shared = shared[&(targets[index].as_str(), cell.platform(&args.platform))]
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner);
+
+                    let start = Instant::now();
+                    let result =
+                        project.build_cell(cell, &args.platform, label, jobs > 1, collector);
+
+                    let status = match result {
+                        Ok(_) => CellStatus::Passed,
+                        Err(err) => {
+                            eprintln!("{err}");
+                            failed.store(true, Ordering::Relaxed);
+                            CellStatus::Failed
+                        }
+                    };
+
+                    if let Ok(mut slot) = results[index].lock() {
+                        *slot = Some(CellResult {
+                            status,
+                            duration: start.elapsed(),
+                        });
+                    }
+                }
+            });
+        }
+    });
+
+    let results: Vec<CellResult> = results
+        .into_iter()
+        .map(|slot| {
+            slot.into_inner().ok().flatten().unwrap_or(CellResult {
+                status: CellStatus::Skipped,
+                duration: Duration::ZERO,
+            })
+        })
+        .collect();
+
+    print_summary(cells, &results, &args.platform);
+
+    let failures = results
+        .iter()
+        .filter(|result| result.status != CellStatus::Passed)
+        .count();
+
+    match failures {
+        0 => Ok(()),
+        failures => Err(UnrealError::MatrixFailed {
+            failures,
+            total: cells.len(),
+        }),
+    }
+}
+
+fn print_summary(cells: &[BuildCell], results: &[CellResult], default_platform: &str) {
+    println!(
+        "\n{:<24} {:<12} {:<10} {:<8} {:>10}",
+        "Target", "Config", "Platform", "Result", "Duration"
+    );
+
+    for (cell, result) in cells.iter().zip(results) {
+        println!(
+            "{:<24} {:<12} {:<10} {:<8} {:>10}",
+            cell.target.to_string(),
+            cell.configuration.as_str(),
+            cell.platform(default_platform),
+            format!("{:?}", result.status),
+            format_duration(result.duration)
+        );
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}m {:02}s", seconds / 60, seconds % 60)
+}
+
+impl FromStr for BuildCell {
+    type Err = String;
+
+    /* Parses `Target:Configuration[:Platform]`, Ex : `Game:Shipping:Linux`. */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(':');
+
+        let (Some(target), Some(configuration)) = (parts.next(), parts.next()) else {
+            return Err(format!("expected Target:Configuration[:Platform] : {s}"));
+        };
+
+        let platform = parts.next().map(str::to_owned);
+
+        if parts.next().is_some() {
+            return Err(format!("expected Target:Configuration[:Platform] : {s}"));
+        }
+
+        Ok(BuildCell {
+            target: target.parse()?,
+            configuration: configuration.parse()?,
+            platform,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unreal_engine::build_target::TargetType;
+
+    #[test]
+    fn parses_cells() {
+        let cases = [
+            (
+                "Target:DebugGame:Win64",
+                TargetSelector::Named(String::from("Target")),
+                BuildConfiguration::DebugGame,
+                Some("Win64"),
+            ),
+            (
+                "Game:Shipping",
+                TargetSelector::Type(TargetType::Game),
+                BuildConfiguration::Shipping,
+                None,
+            ),
+            (
+                " editor:development:Linux ",
+                TargetSelector::Type(TargetType::Editor),
+                BuildConfiguration::Development,
+                Some("Linux"),
+            ),
+        ];
+
+        for (input, target, configuration, platform) in cases {
+            let cell: BuildCell = input.parse().unwrap();
+
+            assert_eq!(cell.target, target, "{input}");
+            assert_eq!(cell.configuration, configuration, "{input}");
+            assert_eq!(cell.platform.as_deref(), platform, "{input}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_cells() {
+        for input in [
+            "Game",
+            "Game:Fast",
+            "Game:Shipping:Linux:Extra",
+            ":Shipping",
+        ] {
+            assert!(input.parse::<BuildCell>().is_err(), "{input}");
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::{fmt::Display, path::Path, str::FromStr};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::unreal_engine::error::UnrealError;
+
+/// Named the way UnrealBuildTool names them on the command line, `debug-game` is kept for older scripts.
+#[derive(ValueEnum, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[value(rename_all = "PascalCase")]
+pub enum BuildConfiguration {
+    Debug,
+    #[value(alias = "debug-game")]
+    DebugGame,
+    Development,
+    Test,
+    Shipping,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum TargetType {
+    Editor,
+    Game,
+    Client,
+    Server,
+    Program,
+}
+
+/// The target requested on the command line, either a target type or an explicit target name.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone)]
+#[serde(into = "String", try_from = "String")]
+pub enum TargetSelector {
+    Type(TargetType),
+    Named(String),
+}
+
+/// A target declared by one of the project's `Source/*.Target.cs` files.
+#[derive(Debug, Clone)]
+pub struct BuildTarget {
+    pub name: String,
+    pub kind: Option<TargetType>,
+}
+
+impl BuildConfiguration {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildConfiguration::Debug => "Debug",
+            BuildConfiguration::DebugGame => "DebugGame",
+            BuildConfiguration::Development => "Development",
+            BuildConfiguration::Test => "Test",
+            BuildConfiguration::Shipping => "Shipping",
+        }
+    }
+}
+
+impl FromStr for BuildConfiguration {
+    type Err = String;
+
+    /* Parses the name UnrealBuildTool uses, ignoring case. */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BuildConfiguration::value_variants()
+            .iter()
+            .find(|configuration| configuration.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| {
+                format!(
+                    "unknown configuration {s}, expected one of : {}",
+                    BuildConfiguration::value_variants()
+                        .iter()
+                        .map(BuildConfiguration::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+impl TargetType {
+    /* The suffix of the conventional target name, `MyGame` for games and `MyGameEditor` for editors. */
+    fn name_suffix(&self) -> &'static str {
+        match self {
+            TargetType::Editor => "Editor",
+            TargetType::Game | TargetType::Program => "",
+            TargetType::Client => "Client",
+            TargetType::Server => "Server",
+        }
+    }
+}
+
+impl TargetSelector {
+    /* Picks the target to build from those declared by the project. */
+    pub fn resolve(
+        &self,
+        project_name: &str,
+        targets: &[BuildTarget],
+    ) -> Result<BuildTarget, UnrealError> {
+        let found = match self {
+            TargetSelector::Named(name) => targets.iter().find(|target| &target.name == name),
+            TargetSelector::Type(kind) => {
+                let conventional = format!("{project_name}{}", kind.name_suffix());
+                let of_type: Vec<_> = targets
+                    .iter()
+                    .filter(|target| target.kind == Some(*kind))
+                    .collect();
+
+                // Falls back to the only target of that type when it doesn't follow the naming convention.
+                match of_type.iter().find(|target| target.name == conventional) {
+                    Some(target) => Some(*target),
+                    None if of_type.len() == 1 => Some(of_type[0]),
+                    None => None,
+                }
+            }
+        };
+
+        found.cloned().ok_or_else(|| UnrealError::TargetNotFound {
+            target: self.to_string(),
+            available: targets
+                .iter()
+                .map(|target| target.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        })
+    }
+}
+
+impl BuildTarget {
+    /* Reads every `*.Target.cs` file in the project's Source directory. */
+    pub fn discover(project_dir: &Path) -> Vec<BuildTarget> {
+        let Ok(entries) = std::fs::read_dir(project_dir.join("Source")) else {
+            return Vec::new();
+        };
+
+        let mut targets: Vec<_> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let name = path.file_name()?.to_str()?.strip_suffix(".Target.cs")?;
+
+                let contents = std::fs::read_to_string(&path).ok()?;
+
+                Some(BuildTarget {
+                    name: name.to_owned(),
+                    kind: parse_target_type(&contents),
+                })
+            })
+            .collect();
+
+        targets.sort_by(|a, b| a.name.cmp(&b.name));
+        targets
+    }
+}
+
+/* Finds the `Type = TargetType.Game;` assignment of a target rules file. */
+fn parse_target_type(contents: &str) -> Option<TargetType> {
+    contents.lines().find_map(|line| {
+        let (_, rest) = line.split_once("TargetType.")?;
+
+        let name: String = rest
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect();
+
+        TargetType::from_str(&name, true).ok()
+    })
+}
+
+impl FromStr for TargetSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s.is_empty() {
+            return Err(String::from("the target can't be empty"));
+        }
+
+        match TargetType::from_str(s, true) {
+            Ok(kind) => Ok(TargetSelector::Type(kind)),
+            Err(_) => Ok(TargetSelector::Named(s.to_owned())),
+        }
+    }
+}
+
+impl TryFrom<String> for TargetSelector {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for TargetSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TargetSelector::Type(kind) => write!(f, "{kind:?}"),
+            TargetSelector::Named(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl From<TargetSelector> for String {
+    fn from(value: TargetSelector) -> Self {
+        value.to_string()
+    }
+}
@@ -1,25 +1,185 @@
 use clap::Subcommand;
 use serde::Serialize;
 
-use crate::unreal_engine::{args::UnrealArgs, error::UnrealError, unreal_project::UnrealProject};
+use crate::{
+    unreal_engine::{
+        args::{
+            BuildArgs, CommandletArgs, GenerateArgs, InfoArgs, LogsArgs, MergeReportsArgs,
+            PackageArgs, PythonArgs, RunArgs, TestArgs, UnrealArgs, ValidateAssetsArgs,
+        },
+        asset_validation, automation, commandlet,
+        error::UnrealError,
+        packaging, project_cache, project_descriptor, project_files, python,
+        unreal_installation::UnrealInstallation,
+        unreal_log,
+        unreal_project::UnrealProject,
+        workspace,
+    },
+    utility::search::SearchOptions,
+};
 
 #[derive(Subcommand, Debug, Clone, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum UnrealCommand {
-    Build,
-    BuildAndRun,
-    Run,
+    Build(BuildArgs),
+    BuildAndRun(BuildArgs),
+    /// starts the editor with the project.
+    Run(RunArgs),
+    /// prints the project's .uproject descriptor and its resolved engine.
+    Info(InfoArgs),
+    /// runs automation tests headlessly and exports a JUnit report.
+    Test(TestArgs),
+    /// builds, cooks, stages and packages the project through RunUAT BuildCookRun.
+    Package(PackageArgs),
+    /// generates IDE project files or a compile_commands.json for the project.
+    Generate(GenerateArgs),
+    /// runs an editor commandlet headlessly, Ex : "unreal commandlet ResavePackages -- -OnlyMaps"
+    Commandlet(CommandletArgs),
+    /// runs a Python script inside the headless editor, Ex : "unreal python fixup.py --dry-run"
+    Python(PythonArgs),
+    /// validates the project's assets with the DataValidation commandlet.
+    ValidateAssets(ValidateAssetsArgs),
+    /// filters an Unreal log file by category and verbosity.
+    Logs(LogsArgs),
+    /// combines the JUnit reports of sharded test runs into one.
+    MergeReports(MergeReportsArgs),
+    /// inspect the engine installations on this machine.
+    #[command(subcommand)]
+    Engines(EnginesCommand),
+    /// inspect or clear the project's resolved engine cache.
+    #[command(subcommand)]
+    Cache(CacheCommand),
+}
+
+#[derive(Subcommand, Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EnginesCommand {
+    /// lists every discovered engine installation.
+    List {
+        #[arg(long)]
+        /// print the installations as json.
+        json: bool,
+
+        #[arg(long)]
+        /// only list registered engines, skipping the search root walk.
+        registry_only: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheCommand {
+    /// prints the cached project and whether it's still valid.
+    Show {
+        #[arg(long)]
+        /// print the cache file as json.
+        json: bool,
+    },
+    /// removes the cached project, the next command resolves it again.
+    Clear,
 }
 
 pub fn process_unreal_command(args: UnrealArgs) -> Result<(), UnrealError> {
     let command = args.command;
     let options = args.search_options;
 
+    if let UnrealCommand::Engines(command) = command {
+        return process_engines_command(command, &options);
+    }
+
+    if let UnrealCommand::Cache(command) = command {
+        return project_cache::process_cache_command(command, &options);
+    }
+
+    if let UnrealCommand::MergeReports(args) = command {
+        return automation::merge_reports(&args);
+    }
+
+    if let UnrealCommand::Logs(args) = command {
+        return unreal_log::process_logs_command(&args);
+    }
+
+    if options.all || !options.projects.is_empty() {
+        return workspace::run(&command, &options);
+    }
+
     let project = UnrealProject::try_from(options)?;
+    run_project_command(project, command)
+}
 
+/* Runs a command that needs a resolved project. */
+pub fn run_project_command(
+    project: UnrealProject,
+    command: UnrealCommand,
+) -> Result<(), UnrealError> {
     match command {
-        UnrealCommand::Build => project.build_project(),
-        UnrealCommand::BuildAndRun => project.build_and_start(),
-        UnrealCommand::Run => project.start_project(),
+        UnrealCommand::Build(args) => project.build_project(&args),
+        UnrealCommand::BuildAndRun(args) => project.build_and_start(&args),
+        UnrealCommand::Run(args) => match args.follow {
+            true => project.follow_project(&args),
+            false => project.start_project(),
+        },
+        UnrealCommand::Info(args) => project_descriptor::print_info(&project, &args),
+        UnrealCommand::Test(args) => automation::run(&project, &args),
+        UnrealCommand::Package(args) => packaging::run(&project, &args),
+        UnrealCommand::Generate(args) => project_files::generate(&project, &args),
+        UnrealCommand::Python(args) => python::run(&project, &args),
+        UnrealCommand::ValidateAssets(args) => asset_validation::run(&project, &args),
+        UnrealCommand::Commandlet(args) => commandlet::process_commandlet_command(&project, &args),
+        UnrealCommand::Engines(_)
+        | UnrealCommand::Cache(_)
+        | UnrealCommand::MergeReports(_)
+        | UnrealCommand::Logs(_) => {
+            unreachable!("handled before resolving the project")
+        }
+    }
+}
+
+fn process_engines_command(
+    command: EnginesCommand,
+    options: &SearchOptions,
+) -> Result<(), UnrealError> {
+    match command {
+        EnginesCommand::List {
+            json,
+            registry_only,
+        } => {
+            let installations = UnrealInstallation::discover_all(options, registry_only);
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&installations)?);
+                return Ok(());
+            }
+
+            if installations.is_empty() {
+                println!("No engine installations found.");
+            }
+
+            for installation in installations {
+                let version = &installation.version;
+
+                println!(
+                    "{} ({:?}) : {}",
+                    version, installation.kind, installation.base_path
+                );
+                println!("    Executable : {}", installation.exe_path);
+                println!("    Branch : {}", version.branch_name);
+                println!(
+                    "    Changelist : {} (Compatible : {})",
+                    version.changelist, version.compatible_changelist
+                );
+                println!(
+                    "    Licensee : {}, Promoted : {}",
+                    version.is_licensee_version != 0,
+                    version.is_promoted_build != 0
+                );
+
+                if let Some(build_id) = &version.build_id {
+                    println!("    Build Id : {build_id}");
+                }
+            }
+
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,171 @@
+use std::{
+    path::Path,
+    process::Command,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::unreal_engine::{
+    args::CommandletArgs,
+    error::UnrealError,
+    host_platform, process,
+    unreal_log::{LogRecord, Verbosity},
+    unreal_project::UnrealProject,
+};
+
+/// What a commandlet run produced, the errors and warnings come from its closing summary when it printed one.
+#[derive(Serialize, Debug, Clone)]
+pub struct CommandletResult {
+    pub name: String,
+    /// none when the editor was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    #[serde(serialize_with = "serialize_seconds")]
+    pub duration: Duration,
+}
+
+/// The lines seen while the commandlet runs.
+#[derive(Default)]
+struct CommandletOutput {
+    in_summary: bool,
+    summary: Option<(Vec<String>, Vec<String>)>,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+const CI_FLAGS: &[&str] = &[
+    "-unattended",
+    "-nop4",
+    "-nosplash",
+    "-nullrhi",
+    "-stdout",
+    "-FullStdOutLogOutput",
+];
+
+impl CommandletResult {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/* Runs the commandlet through the command line editor, a failing commandlet is still a result. */
+pub fn run(
+    project: &UnrealProject,
+    name: &str,
+    extra: &[String],
+) -> Result<CommandletResult, UnrealError> {
+    let exe_path = host_platform::editor_cmd_executable(Path::new(&project.engine().exe_path));
+
+    let mut command = Command::new(exe_path);
+    command
+        .arg(project.path())
+        .arg(format!("-run={name}"))
+        .args(CI_FLAGS)
+        .args(extra);
+
+    let output = Mutex::new(CommandletOutput::default());
+    let observer = |line: &str| {
+        if let Ok(mut output) = output.lock() {
+            output.observe(line);
+        }
+    };
+
+    println!("\nRunning Commandlet : {name}..");
+    let start = Instant::now();
+
+    let mut child = process::create_child_process(command)?;
+    process::monitor_output(&mut child, None, Some(&observer))?;
+    let status = child.wait()?;
+
+    let output = output.into_inner().unwrap_or_default();
+
+    let (errors, warnings) = match output.summary {
+        Some(summary) => summary,
+        None => (output.errors, output.warnings),
+    };
+
+    Ok(CommandletResult {
+        name: name.to_owned(),
+        exit_code: status.code(),
+        errors,
+        warnings,
+        duration: start.elapsed(),
+    })
+}
+
+/* Runs the commandlet requested on the command line, printing its summary. */
+pub fn process_commandlet_command(
+    project: &UnrealProject,
+    args: &CommandletArgs,
+) -> Result<(), UnrealError> {
+    let result = run(project, &args.name, &args.extra)?;
+
+    println!(
+        "\nCommandlet {} : {} error(s), {} warning(s) in {:.1}s.",
+        result.name,
+        result.errors.len(),
+        result.warnings.len(),
+        result.duration.as_secs_f64()
+    );
+
+    for line in result.errors.iter().chain(&result.warnings) {
+        println!("    {line}");
+    }
+
+    if let Some(path) = &args.json {
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(file, &result)?;
+    }
+
+    match result.succeeded() {
+        true => Ok(()),
+        false => Err(UnrealError::CommandletFailed {
+            errors: result.errors.len(),
+            code: result.exit_code,
+            name: result.name,
+        }),
+    }
+}
+
+impl CommandletOutput {
+    /* The editor closes with `Warning/Error Summary (Unique only)`, a dashed line, the unique messages,
+    then `Success - 0 error(s), 1 warning(s)`. */
+    fn observe(&mut self, line: &str) {
+        let record = LogRecord::parse(line);
+
+        if record.message.contains("Warning/Error Summary") {
+            self.in_summary = true;
+            self.summary = Some((Vec::new(), Vec::new()));
+            return;
+        }
+
+        if self.in_summary && record.message.contains(" error(s), ") {
+            self.in_summary = false;
+            return;
+        }
+
+        // The summary repeats each line as the message of a LogInit one.
+        let (record, (errors, warnings)) = match (&mut self.summary, self.in_summary) {
+            (Some((errors, warnings)), true) => {
+                (LogRecord::parse(&record.message), (errors, warnings))
+            }
+            _ => (record, (&mut self.errors, &mut self.warnings)),
+        };
+
+        match record.verbosity {
+            Verbosity::Fatal | Verbosity::Error => errors.push(record.categorized()),
+            Verbosity::Warning => warnings.push(record.categorized()),
+            _ => {}
+        }
+    }
+}
+
+fn serialize_seconds<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
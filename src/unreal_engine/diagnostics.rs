@@ -0,0 +1,346 @@
+use std::{collections::HashSet, path::Path, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::unreal_engine::error::UnrealError;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A compiler, linker or UnrealBuildTool message parsed from the build output.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Diagnostic {
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub severity: Severity,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// Collects the diagnostics of one or more builds, dropping the duplicates UBT prints in its summary.
+#[derive(Default)]
+pub struct DiagnosticCollector {
+    diagnostics: Mutex<(Vec<Diagnostic>, HashSet<Diagnostic>)>,
+}
+
+impl DiagnosticCollector {
+    pub fn observe(&self, line: &str) {
+        let Some(diagnostic) = parse_line(line) else {
+            return;
+        };
+
+        if let Ok(mut guard) = self.diagnostics.lock() {
+            let (diagnostics, seen) = &mut *guard;
+
+            if seen.insert(diagnostic.clone()) {
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics
+            .into_inner()
+            .map(|(diagnostics, _)| diagnostics)
+            .unwrap_or_default()
+    }
+}
+
+/* Prints the error and warning count. */
+pub fn print_summary(diagnostics: &[Diagnostic]) {
+    let errors = count(diagnostics, Severity::Error);
+    let warnings = count(diagnostics, Severity::Warning);
+
+    println!("\nDiagnostics : {errors} error(s), {warnings} warning(s).");
+}
+
+pub fn count(diagnostics: &[Diagnostic], severity: Severity) -> usize {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == severity)
+        .count()
+}
+
+pub fn write_json(diagnostics: &[Diagnostic], path: &Path) -> Result<(), UnrealError> {
+    let file = std::fs::File::create(path)?;
+    serde_json::to_writer_pretty(file, diagnostics)?;
+    Ok(())
+}
+
+const MARKERS: &[(&str, Severity)] = &[
+    (": fatal error", Severity::Error),
+    (": error", Severity::Error),
+    (": warning", Severity::Warning),
+];
+
+/* Parses MSVC `file(line,col): error C1234: message`, clang `file:line:col: error: message`,
+linker and UnrealBuildTool `ERROR: message` lines. */
+pub fn parse_line(line: &str) -> Option<Diagnostic> {
+    let line = line.trim();
+    let lower = line.to_ascii_lowercase();
+
+    if let Some(diagnostic) = parse_tool_message(line, &lower) {
+        return Some(diagnostic);
+    }
+
+    let (index, marker, severity) = MARKERS
+        .iter()
+        .filter_map(|(marker, severity)| {
+            let index = find_marker(&lower, marker)?;
+            Some((index, *marker, *severity))
+        })
+        .min_by_key(|(index, ..)| *index)?;
+
+    let location = &line[..index];
+    let rest = line[index + marker.len()..].trim_start();
+
+    let (code, message) = split_code(rest);
+    let position = parse_location(location);
+
+    let (file, line, column) = match position {
+        Some((file, line, column)) => (Some(file.to_owned()), Some(line), column),
+        None => (None, None, None),
+    };
+
+    Some(Diagnostic {
+        file,
+        line,
+        column,
+        severity,
+        code,
+        message,
+    })
+}
+
+/* UnrealBuildTool's own `ERROR: ...` / `WARNING: ...` lines, and GNU ld's undefined references. */
+fn parse_tool_message(line: &str, lower: &str) -> Option<Diagnostic> {
+    let (severity, message) = if lower.starts_with("error:") {
+        (Severity::Error, &line["error:".len()..])
+    } else if lower.starts_with("warning:") {
+        (Severity::Warning, &line["warning:".len()..])
+    } else if lower.contains("undefined reference to") {
+        (Severity::Error, line)
+    } else {
+        return None;
+    };
+
+    Some(Diagnostic {
+        file: None,
+        line: None,
+        column: None,
+        severity,
+        code: None,
+        message: message.trim().to_owned(),
+    })
+}
+
+/* Finds the marker when it's followed by a separator, so `: errors` or `: warnings` don't match. */
+fn find_marker(lower: &str, marker: &str) -> Option<usize> {
+    lower.match_indices(marker).find_map(|(index, _)| {
+        match lower[index + marker.len()..].chars().next() {
+            None | Some(' ') | Some(':') => Some(index),
+            _ => None,
+        }
+    })
+}
+
+/* Splits `C4996: message` and `: message [-Wunused]` into the code and the message. */
+fn split_code(rest: &str) -> (Option<String>, String) {
+    if let Some(message) = rest.strip_prefix(':') {
+        return clang_flag(message.trim());
+    }
+
+    match rest.split_once(':') {
+        Some((code, message)) if !code.is_empty() && !code.contains(char::is_whitespace) => {
+            (Some(code.to_owned()), message.trim().to_owned())
+        }
+        _ => clang_flag(rest.trim()),
+    }
+}
+
+fn clang_flag(message: &str) -> (Option<String>, String) {
+    let flag = message
+        .strip_suffix(']')
+        .and_then(|message| message.rsplit_once(" [-W"));
+
+    match flag {
+        Some((message, flag)) => (Some(format!("-W{flag}")), message.trim().to_owned()),
+        None => (None, message.to_owned()),
+    }
+}
+
+/* Parses `file(line)`, `file(line,col)`, `file:line` and `file:line:col`. */
+fn parse_location(location: &str) -> Option<(&str, u32, Option<u32>)> {
+    let location = location.trim();
+
+    if let Some(inner) = location.strip_suffix(')') {
+        let (file, position) = inner.rsplit_once('(')?;
+        let mut numbers = position.split(',').map(leading_number);
+
+        let line = numbers.next()??;
+        let column = numbers.next().flatten();
+
+        return Some((file.trim(), line, column)).filter(|(file, ..)| !file.is_empty());
+    }
+
+    let (rest, last) = location.rsplit_once(':')?;
+    let last = last.parse::<u32>().ok()?;
+
+    let position = rest
+        .rsplit_once(':')
+        .and_then(|(file, line)| Some((file, line.parse::<u32>().ok()?, Some(last))))
+        .unwrap_or((rest, last, None));
+
+    Some(position).filter(|(file, ..)| !file.is_empty())
+}
+
+fn leading_number(text: &str) -> Option<u32> {
+    let digits: String = text
+        .trim()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(
+        location: Option<(&str, u32, Option<u32>)>,
+        severity: Severity,
+        code: Option<&str>,
+        message: &str,
+    ) -> Diagnostic {
+        Diagnostic {
+            file: location.map(|(file, ..)| file.to_owned()),
+            line: location.map(|(_, line, _)| line),
+            column: location.and_then(|(.., column)| column),
+            severity,
+            code: code.map(str::to_owned),
+            message: message.to_owned(),
+        }
+    }
+
+    #[test]
+    fn parses_compiler_and_tool_lines() {
+        let cases = [
+            (
+                r"C:\Game\Source\Actor.cpp(12,5): error C2065: 'x': undeclared identifier",
+                diagnostic(
+                    Some((r"C:\Game\Source\Actor.cpp", 12, Some(5))),
+                    Severity::Error,
+                    Some("C2065"),
+                    "'x': undeclared identifier",
+                ),
+            ),
+            (
+                r"D:\Game\Source\Actor.h(7): warning C4996: 'strcpy': This function may be unsafe.",
+                diagnostic(
+                    Some((r"D:\Game\Source\Actor.h", 7, None)),
+                    Severity::Warning,
+                    Some("C4996"),
+                    "'strcpy': This function may be unsafe.",
+                ),
+            ),
+            (
+                "/game/Source/Actor.cpp:10:3: error: use of undeclared identifier 'y'",
+                diagnostic(
+                    Some(("/game/Source/Actor.cpp", 10, Some(3))),
+                    Severity::Error,
+                    None,
+                    "use of undeclared identifier 'y'",
+                ),
+            ),
+            (
+                "  /game/Source/Actor.cpp:4:9: warning: unused variable 'z' [-Wunused-variable]",
+                diagnostic(
+                    Some(("/game/Source/Actor.cpp", 4, Some(9))),
+                    Severity::Warning,
+                    Some("-Wunused-variable"),
+                    "unused variable 'z'",
+                ),
+            ),
+            (
+                "/game/Source/Actor.cpp:1:10: fatal error: 'Missing.h' file not found",
+                diagnostic(
+                    Some(("/game/Source/Actor.cpp", 1, Some(10))),
+                    Severity::Error,
+                    None,
+                    "'Missing.h' file not found",
+                ),
+            ),
+            (
+                "ERROR: Unable to find target 'DemoServer'",
+                diagnostic(
+                    None,
+                    Severity::Error,
+                    None,
+                    "Unable to find target 'DemoServer'",
+                ),
+            ),
+            (
+                "Warning: Plugin 'Foo' is missing its descriptor",
+                diagnostic(
+                    None,
+                    Severity::Warning,
+                    None,
+                    "Plugin 'Foo' is missing its descriptor",
+                ),
+            ),
+            (
+                "/usr/bin/ld: Actor.o: in function `Tick': undefined reference to `Missing()'",
+                diagnostic(
+                    None,
+                    Severity::Error,
+                    None,
+                    "/usr/bin/ld: Actor.o: in function `Tick': undefined reference to `Missing()'",
+                ),
+            ),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(parse_line(line), Some(expected), "{line}");
+        }
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        for line in [
+            "",
+            "Building 42 actions with 8 processes...",
+            "[3/42] Compile Actor.cpp",
+            "Summary: errors and warnings are listed below",
+            "/game/Source/Actor.cpp:3:1: note: previous definition is here",
+        ] {
+            assert_eq!(parse_line(line), None, "{line}");
+        }
+    }
+
+    #[test]
+    fn collector_drops_repeated_diagnostics() {
+        let collector = DiagnosticCollector::default();
+
+        for line in [
+            "/game/Source/Actor.cpp:4:9: warning: unused variable 'z' [-Wunused-variable]",
+            "Building 42 actions with 8 processes...",
+            "/game/Source/Actor.cpp:4:9: warning: unused variable 'z' [-Wunused-variable]",
+            "ERROR: Build failed",
+        ] {
+            collector.observe(line);
+        }
+
+        let diagnostics = collector.into_diagnostics();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(count(&diagnostics, Severity::Warning), 1);
+        assert_eq!(count(&diagnostics, Severity::Error), 1);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// The `EngineAssociation` of a .uproject file.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(from = "String", into = "String")]
+pub enum EngineAssociation {
+    /// a launcher engine version, Ex : `5.6`.
+    Version { major: u16, minor: u16 },
+    /// a source build registered under a GUID, Ex : `{A1B2C3D4-...}`.
+    Guid(String),
+    /// a source build registered under a custom name by the version selector.
+    Custom(String),
+    /// an empty association, the project lives inside the engine's source tree.
+    InTree,
+}
+
+impl EngineAssociation {
+    /* Returns the key used by the source build registry, if the association is one. */
+    pub fn registry_key(&self) -> Option<&str> {
+        match self {
+            EngineAssociation::Guid(key) | EngineAssociation::Custom(key) => Some(key),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for EngineAssociation {
+    fn from(value: &str) -> Self {
+        let value = value.trim();
+
+        if value.is_empty() {
+            return EngineAssociation::InTree;
+        }
+
+        if value.starts_with('{') && value.ends_with('}') {
+            return EngineAssociation::Guid(value.to_owned());
+        }
+
+        let version = value
+            .split_once('.')
+            .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)));
+
+        match version {
+            Some((major, minor)) => EngineAssociation::Version { major, minor },
+            None => EngineAssociation::Custom(value.to_owned()),
+        }
+    }
+}
+
+impl From<String> for EngineAssociation {
+    fn from(value: String) -> Self {
+        EngineAssociation::from(value.as_str())
+    }
+}
+
+impl From<EngineAssociation> for String {
+    fn from(value: EngineAssociation) -> Self {
+        value.to_string()
+    }
+}
+
+impl Display for EngineAssociation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngineAssociation::Version { major, minor } => write!(f, "{major}.{minor}"),
+            EngineAssociation::Guid(key) | EngineAssociation::Custom(key) => write!(f, "{key}"),
+            EngineAssociation::InTree => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_associations() {
+        let cases = [
+            ("5.6", EngineAssociation::Version { major: 5, minor: 6 }),
+            (
+                " 4.27 ",
+                EngineAssociation::Version {
+                    major: 4,
+                    minor: 27,
+                },
+            ),
+            (
+                "{A1B2C3D4-0000-1111-2222-333344445555}",
+                EngineAssociation::Guid("{A1B2C3D4-0000-1111-2222-333344445555}".to_owned()),
+            ),
+            ("MyEngine", EngineAssociation::Custom("MyEngine".to_owned())),
+            ("5.6.1", EngineAssociation::Custom("5.6.1".to_owned())),
+            (
+                "{Unclosed",
+                EngineAssociation::Custom("{Unclosed".to_owned()),
+            ),
+            ("", EngineAssociation::InTree),
+            ("   ", EngineAssociation::InTree),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(EngineAssociation::from(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        for input in ["\"5.6\"", "\"{A1B2-C3D4}\"", "\"MyEngine\"", "\"\""] {
+            let association: EngineAssociation = serde_json::from_str(input).unwrap();
+
+            assert_eq!(serde_json::to_string(&association).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn only_source_builds_have_a_registry_key() {
+        let cases = [
+            ("5.6", None),
+            ("{A1B2-C3D4}", Some("{A1B2-C3D4}")),
+            ("MyEngine", Some("MyEngine")),
+            ("", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                EngineAssociation::from(input).registry_key(),
+                expected,
+                "{input}"
+            );
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    unreal_engine::unreal_installation::{InstallationKind, UnrealInstallation},
+    utility::search::SearchOptions,
+};
+
+/// Bumped whenever the index layout changes, older indexes are walked again.
+const INDEX_VERSION: u32 = 1;
+const INDEX_FILE: &str = "engines.bin";
+
+/// The engines found by walking the search roots, shared by every project on the machine.
+#[derive(Serialize, Deserialize)]
+pub struct EngineIndex {
+    version: u32,
+    /// the search roots walked so far.
+    roots: Vec<IndexedRoot>,
+    engines: Vec<IndexedEngine>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+struct IndexedRoot {
+    path: String,
+    max_depth: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct IndexedEngine {
+    exe_path: String,
+    base_path: String,
+    kind: InstallationKind,
+    /// the contents of `Engine/Build/Build.version` when the engine was found, its version is parsed from it.
+    build_version: String,
+}
+
+impl Default for EngineIndex {
+    fn default() -> Self {
+        EngineIndex {
+            version: INDEX_VERSION,
+            roots: Vec::new(),
+            engines: Vec::new(),
+        }
+    }
+}
+
+impl EngineIndex {
+    /* Loads the machine's index, an index that is missing, unreadable or from another version is empty. */
+    pub fn load() -> EngineIndex {
+        let Some(contents) = index_path().and_then(|path| std::fs::read(path).ok()) else {
+            return EngineIndex::default();
+        };
+
+        let config = bincode::config::standard();
+
+        // The version leads the index, so it's read alone before the rest of the layout is trusted.
+        match bincode::serde::decode_from_slice::<u32, _>(&contents, config) {
+            Ok((INDEX_VERSION, _)) => {}
+            _ => return EngineIndex::default(),
+        }
+
+        bincode::serde::decode_from_slice(&contents, config)
+            .map(|(index, _)| index)
+            .unwrap_or_default()
+    }
+
+    /* Writes the index through a temporary file, so a project reading it concurrently never sees half of it. */
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = index_path().ok_or_else(|| io::Error::other("no cache directory"))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        std::fs::create_dir_all(dir)?;
+
+        let contents = bincode::serde::encode_to_vec(self, bincode::config::standard())
+            .map_err(io::Error::other)?;
+
+        let mut file = tempfile::NamedTempFile::new_in(dir)?;
+        file.write_all(&contents)?;
+        file.persist(&path).map_err(|err| err.error)?;
+
+        Ok(path)
+    }
+
+    /* Drops the engines whose Build.version is gone or changed, or whose editor is gone. Returns whether any was dropped. */
+    pub fn revalidate(&mut self) -> bool {
+        let count = self.engines.len();
+
+        self.engines.retain(|engine| {
+            let build_version = std::fs::read_to_string(build_version_path(&engine.base_path));
+
+            build_version.is_ok_and(|contents| contents == engine.build_version)
+                && Path::new(&engine.exe_path).exists()
+        });
+
+        self.engines.len() != count
+    }
+
+    /* The search roots the index doesn't cover, either never walked or walked less deep. */
+    pub fn uncovered_roots(&self, search_options: &SearchOptions) -> Vec<String> {
+        search_options
+            .search_dir
+            .iter()
+            .filter(|root| {
+                !self.roots.iter().any(|indexed| {
+                    indexed.path == **root && indexed.max_depth >= search_options.max_depth
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /* Records a walk of the roots and the engines it found. */
+    pub fn record(&mut self, roots: &[String], max_depth: usize, found: &[UnrealInstallation]) {
+        self.roots.retain(|indexed| !roots.contains(&indexed.path));
+        self.roots.extend(roots.iter().map(|path| IndexedRoot {
+            path: path.clone(),
+            max_depth,
+        }));
+
+        for installation in found {
+            if self
+                .engines
+                .iter()
+                .any(|engine| engine.base_path == installation.base_path)
+            {
+                continue;
+            }
+
+            let Ok(build_version) =
+                std::fs::read_to_string(build_version_path(&installation.base_path))
+            else {
+                continue;
+            };
+
+            self.engines.push(IndexedEngine {
+                exe_path: installation.exe_path.clone(),
+                base_path: installation.base_path.clone(),
+                kind: installation.kind,
+                build_version,
+            });
+        }
+    }
+
+    pub fn installations(&self) -> Vec<UnrealInstallation> {
+        self.engines
+            .iter()
+            .filter_map(|engine| {
+                Some(UnrealInstallation {
+                    exe_path: engine.exe_path.clone(),
+                    base_path: engine.base_path.clone(),
+                    version: serde_json::from_str(&engine.build_version).ok()?,
+                    kind: engine.kind,
+                })
+            })
+            .collect()
+    }
+}
+
+fn build_version_path(base_path: &str) -> PathBuf {
+    Path::new(base_path)
+        .join("Engine")
+        .join("Build")
+        .join("Build.version")
+}
+
+fn index_path() -> Option<PathBuf> {
+    Some(cache_dir()?.join("ci_helper").join(INDEX_FILE))
+}
+
+/* `$XDG_CACHE_HOME`, falling back to `~/.cache`. */
+#[cfg(not(target_os = "windows"))]
+fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join(".cache"))
+}
+
+#[cfg(target_os = "windows")]
+fn cache_dir() -> Option<PathBuf> {
+    let local_app_data = std::env::var_os("LOCALAPPDATA")?;
+    Some(PathBuf::from(local_app_data))
+}
@@ -0,0 +1,354 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// An engine root registered by the launcher or by a source build.
+#[derive(Debug, Clone)]
+pub struct RegistryEntry {
+    /// the key the engine is registered under, a GUID or custom name for source builds and `5.6` for launcher installs.
+    pub association: String,
+    /// the engine root, the directory containing the `Engine` folder.
+    pub root: PathBuf,
+}
+
+/// Where the engines are registered, the user's files unless a test points them elsewhere.
+#[derive(Debug, Clone, Default)]
+pub struct RegistryPaths {
+    /// the source build registry, `Install.ini`.
+    pub install_ini: Option<PathBuf>,
+    /// the launcher's registry, `LauncherInstalled.dat`.
+    pub launcher_installed: Option<PathBuf>,
+    /// whether the source builds Windows registers under `HKEY_CURRENT_USER` are read.
+    pub windows_registry: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LauncherInstalled {
+    installation_list: Vec<LauncherInstallation>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LauncherInstallation {
+    install_location: String,
+    app_name: String,
+}
+
+impl RegistryPaths {
+    /* The registries under a home directory, the way Linux and Mac lay them out in `~/.config/Epic`. */
+    #[cfg_attr(target_os = "windows", allow(dead_code))]
+    pub fn under_home(home: &Path) -> RegistryPaths {
+        let epic = home.join(".config").join("Epic");
+
+        RegistryPaths {
+            install_ini: Some(epic.join("UnrealEngine").join("Install.ini")),
+            launcher_installed: Some(
+                epic.join("UnrealEngineLauncher")
+                    .join("LauncherInstalled.dat"),
+            ),
+            windows_registry: false,
+        }
+    }
+
+    /* The current user's registries, under `$HOME`. */
+    #[cfg(not(target_os = "windows"))]
+    pub fn from_env() -> RegistryPaths {
+        match std::env::var_os("HOME") {
+            Some(home) => RegistryPaths::under_home(Path::new(&home)),
+            None => RegistryPaths::default(),
+        }
+    }
+
+    /* Windows keeps source builds in its registry instead of an ini file, and the launcher's registry is shared by
+    every user under `%PROGRAMDATA%`. */
+    #[cfg(target_os = "windows")]
+    pub fn from_env() -> RegistryPaths {
+        RegistryPaths {
+            install_ini: None,
+            windows_registry: true,
+            launcher_installed: std::env::var_os("PROGRAMDATA").map(|program_data| {
+                PathBuf::from(program_data)
+                    .join("Epic")
+                    .join("UnrealEngineLauncher")
+                    .join("LauncherInstalled.dat")
+            }),
+        }
+    }
+}
+
+/* Returns every registered engine, source builds first. */
+pub fn registered_engines(paths: &RegistryPaths) -> Vec<RegistryEntry> {
+    let mut entries = source_builds(paths);
+    entries.extend(launcher_installs(paths));
+    entries
+}
+
+/* Reads the source build registry, `~/.config/Epic/UnrealEngine/Install.ini` on Linux and Mac and
+`HKCU\Software\Epic Games\Unreal Engine\Builds` on Windows. */
+pub fn source_builds(paths: &RegistryPaths) -> Vec<RegistryEntry> {
+    let mut entries = paths
+        .install_ini
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| parse_install_ini(&contents))
+        .unwrap_or_default();
+
+    if paths.windows_registry {
+        entries.extend(windows_source_builds());
+    }
+
+    entries
+}
+
+/* Finds the source build registered under the given GUID or custom name. */
+pub fn find_source_build(paths: &RegistryPaths, key: &str) -> Option<RegistryEntry> {
+    let key = normalize_key(key);
+
+    source_builds(paths)
+        .into_iter()
+        .find(|entry| normalize_key(&entry.association).eq_ignore_ascii_case(key))
+}
+
+/* Reads the launcher's `LauncherInstalled.dat`, skipping anything that isn't an engine. */
+pub fn launcher_installs(paths: &RegistryPaths) -> Vec<RegistryEntry> {
+    let Some(path) = &paths.launcher_installed else {
+        return Vec::new();
+    };
+
+    let Ok(contents) = std::fs::read(path) else {
+        return Vec::new();
+    };
+
+    let Ok(installed) = serde_json::from_slice::<LauncherInstalled>(&contents) else {
+        return Vec::new();
+    };
+
+    installed
+        .installation_list
+        .into_iter()
+        .filter_map(|installation| {
+            let version = installation.app_name.strip_prefix("UE_")?;
+
+            Some(RegistryEntry {
+                association: version.to_owned(),
+                root: PathBuf::from(installation.install_location),
+            })
+        })
+        .collect()
+}
+
+fn normalize_key(key: &str) -> &str {
+    key.trim().trim_start_matches('{').trim_end_matches('}')
+}
+
+fn parse_install_ini(contents: &str) -> Vec<RegistryEntry> {
+    let mut in_installations = false;
+    let mut entries = Vec::new();
+
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_installations = section.eq_ignore_ascii_case("Installations");
+            continue;
+        }
+
+        if !in_installations {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            entries.push(RegistryEntry {
+                association: key.trim().to_owned(),
+                root: PathBuf::from(value.trim()),
+            });
+        }
+    }
+
+    entries
+}
+
+// Only Windows has a registry.
+#[cfg(not(target_os = "windows"))]
+fn windows_source_builds() -> Vec<RegistryEntry> {
+    Vec::new()
+}
+
+/* Reads the string values of the Builds key, each names an engine root after the GUID or custom name it's
+registered under. */
+#[cfg(target_os = "windows")]
+fn windows_source_builds() -> Vec<RegistryEntry> {
+    use windows::{
+        Win32::{
+            Foundation::{ERROR_NO_MORE_ITEMS, ERROR_SUCCESS},
+            System::Registry::{
+                HKEY, HKEY_CURRENT_USER, KEY_READ, REG_SZ, RegCloseKey, RegEnumValueW,
+                RegOpenKeyExW,
+            },
+        },
+        core::{PWSTR, w},
+    };
+
+    let mut key = HKEY::default();
+
+    // SAFETY: the subkey is a static wide string and `key` outlives the call.
+    let opened = unsafe {
+        RegOpenKeyExW(
+            HKEY_CURRENT_USER,
+            w!(r"Software\Epic Games\Unreal Engine\Builds"),
+            None,
+            KEY_READ,
+            &mut key,
+        )
+    };
+
+    if opened != ERROR_SUCCESS {
+        return Vec::new();
+    }
+
+    let mut entries = Vec::new();
+
+    // Value names are at most 16383 characters, an engine path fits in 32767 of them.
+    let mut name = vec![0u16; 16384];
+    let mut data = vec![0u8; 65536];
+
+    for index in 0u32.. {
+        let mut name_len = name.len() as u32;
+        let mut data_len = data.len() as u32;
+        let mut kind = 0u32;
+
+        // SAFETY: the buffers and their lengths match, and they outlive the call.
+        let result = unsafe {
+            RegEnumValueW(
+                key,
+                index,
+                Some(PWSTR(name.as_mut_ptr())),
+                &mut name_len,
+                None,
+                Some(&mut kind),
+                Some(data.as_mut_ptr()),
+                Some(&mut data_len),
+            )
+        };
+
+        if result == ERROR_NO_MORE_ITEMS {
+            break;
+        }
+
+        if result != ERROR_SUCCESS || kind != REG_SZ.0 {
+            continue;
+        }
+
+        let value: Vec<u16> = data[..data_len as usize]
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+
+        entries.push(RegistryEntry {
+            association: String::from_utf16_lossy(&name[..name_len as usize]),
+            root: PathBuf::from(String::from_utf16_lossy(&value).trim_end_matches('\0')),
+        });
+    }
+
+    // SAFETY: the key was opened above and isn't used after.
+    unsafe {
+        let _ = RegCloseKey(key);
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTALL_INI: &str = "\
+[Installations]
+{2A1B3C4D-0000-1111-2222-333344445555}=/home/dev/UnrealEngine
+; a comment
+MyEngine = /opt/MyEngine
+
+[Other]
+Ignored=/nowhere
+";
+
+    const LAUNCHER_INSTALLED: &str = r#"{
+	"InstallationList": [
+		{
+			"InstallLocation": "/opt/Epic/UE_5.4",
+			"NamespaceId": "ue",
+			"ItemId": "",
+			"ArtifactId": "",
+			"AppVersion": "5.4.4-35576357+++UE5+Release-5.4-Linux",
+			"AppName": "UE_5.4"
+		},
+		{
+			"InstallLocation": "/opt/Epic/Fab",
+			"AppName": "FabPlugin"
+		}
+	]
+}"#;
+
+    /* A fake home holding both registries. */
+    fn fake_home() -> (tempfile::TempDir, RegistryPaths) {
+        let home = tempfile::tempdir().unwrap();
+        let paths = RegistryPaths::under_home(home.path());
+
+        for (path, contents) in [
+            (&paths.install_ini, INSTALL_INI),
+            (&paths.launcher_installed, LAUNCHER_INSTALLED),
+        ] {
+            let path = path.as_ref().unwrap();
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        (home, paths)
+    }
+
+    #[test]
+    fn reads_the_installations_section_of_install_ini() {
+        let (_home, paths) = fake_home();
+
+        let entries = source_builds(&paths);
+        let associations: Vec<&str> = entries.iter().map(|e| e.association.as_str()).collect();
+
+        assert_eq!(
+            associations,
+            ["{2A1B3C4D-0000-1111-2222-333344445555}", "MyEngine"]
+        );
+        assert_eq!(entries[1].root, PathBuf::from("/opt/MyEngine"));
+    }
+
+    #[test]
+    fn finds_a_source_build_with_or_without_braces() {
+        let (_home, paths) = fake_home();
+
+        let entry = find_source_build(&paths, "2a1b3c4d-0000-1111-2222-333344445555").unwrap();
+        assert_eq!(entry.root, PathBuf::from("/home/dev/UnrealEngine"));
+
+        assert!(find_source_build(&paths, "{MyEngine}").is_some());
+        assert!(find_source_build(&paths, "Ignored").is_none());
+    }
+
+    #[test]
+    fn reads_only_engines_from_launcher_installed() {
+        let (_home, paths) = fake_home();
+
+        let entries = launcher_installs(&paths);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].association, "5.4");
+        assert_eq!(entries[0].root, PathBuf::from("/opt/Epic/UE_5.4"));
+    }
+
+    #[test]
+    fn missing_registries_are_empty() {
+        let home = tempfile::tempdir().unwrap();
+
+        assert!(registered_engines(&RegistryPaths::under_home(home.path())).is_empty());
+    }
+}
@@ -7,12 +7,110 @@ pub enum UnrealError {
     #[error("Failed to find Unreal Engine Path.")]
     EngineNotFound,
 
+    #[error("Failed to find an Unreal Engine matching : {requirement}")]
+    EngineVersionNotFound { requirement: String },
+
     #[error("Failed to find Unreal Engine Project.")]
     ProjectNotFound,
 
+    #[error("Failed to find the Unreal Engine Project : {name}")]
+    NamedProjectNotFound { name: String },
+
+    #[error("Found several projects, pick one with --project : [{candidates}]")]
+    AmbiguousProject { candidates: String },
+
+    #[error("No source build is registered as : {association}")]
+    UnregisteredEngine { association: String },
+
+    #[error("Failed to find the engine tree containing the project : {project}")]
+    InTreeEngineNotFound { project: String },
+
+    #[error("Unknown build target {target}, the project declares : [{available}]")]
+    TargetNotFound { target: String, available: String },
+
+    #[error("Invalid build matrix : {reason}")]
+    InvalidMatrix { reason: String },
+
+    #[error("{failures} of {total} matrix cells did not pass.")]
+    MatrixFailed { failures: usize, total: usize },
+
+    #[error("{failures} of {total} projects did not pass.")]
+    WorkspaceFailed { failures: usize, total: usize },
+
+    #[error("Invalid warning baseline : {reason}")]
+    InvalidBaseline { reason: String },
+
+    #[error("The build introduced {count} new warning(s).")]
+    NewWarnings { count: usize },
+
+    #[error("The automation report wasn't written : {path}")]
+    ReportNotFound { path: String },
+
+    #[error("No automation tests match the filter : {filter}")]
+    NoTestsFound { filter: String },
+
+    #[error("Invalid quarantine file : {reason}")]
+    InvalidQuarantine { reason: String },
+
+    #[error("{failed} of {total} automation tests failed.")]
+    TestsFailed { failed: usize, total: usize },
+
+    #[error("UnrealBuildTool didn't write the compile commands : {path}")]
+    ClangDatabaseNotFound { path: String },
+
+    #[error("AutomationTool failed with ExitCode={code} ({reason}).")]
+    AutomationToolFailed { code: i32, reason: String },
+
+    #[error("The {name} commandlet failed with exit code {code:?} and {errors} error(s).")]
+    CommandletFailed {
+        name: String,
+        code: Option<i32>,
+        errors: usize,
+    },
+
+    #[error("The {plugin} plugin isn't enabled in {project}, add it to the .uproject's Plugins.")]
+    PluginNotEnabled { plugin: String, project: String },
+
+    #[error("Failed to find the Python script : {script}")]
+    PythonScriptNotFound { script: String },
+
+    #[error("The Python script {script} exited with code {code}.")]
+    PythonScriptFailed { script: String, code: i32 },
+
+    #[error("The editor closed before the Python script {script} returned.")]
+    PythonScriptUnfinished { script: String },
+
+    #[error("Asset validation failed for {assets} asset(s).")]
+    AssetValidationFailed { assets: usize },
+
+    #[error("Failed to find the engine script : {path}")]
+    ScriptNotFound { path: String },
+
+    #[error("Failed to start {program} : {error}")]
+    SpawnFailed {
+        program: String,
+        #[source]
+        error: io::Error,
+    },
+
+    #[error("The child process output was not captured.")]
+    OutputNotCaptured,
+
     #[error("Failed exit status : {status}")]
     FailedExitStatus { status: ExitStatus },
 
+    #[error("{error}")]
+    GitError {
+        #[from]
+        error: git2::Error,
+    },
+
+    #[error("{error}")]
+    JsonError {
+        #[from]
+        error: serde_json::Error,
+    },
+
     #[error("{error}")]
     IoError {
         #[from]
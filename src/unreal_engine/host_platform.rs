@@ -0,0 +1,101 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::unreal_engine::{error::UnrealError, unreal_installation::UnrealInstallation};
+
+/// the UnrealBuildTool platform name of the machine running the tool.
+#[cfg(target_os = "windows")]
+pub const PLATFORM: &str = "Win64";
+#[cfg(target_os = "linux")]
+pub const PLATFORM: &str = "Linux";
+#[cfg(target_os = "macos")]
+pub const PLATFORM: &str = "Mac";
+
+#[cfg(target_os = "windows")]
+const BUILD_SCRIPT: &[&str] = &["Build.bat"];
+#[cfg(target_os = "linux")]
+const BUILD_SCRIPT: &[&str] = &["Linux", "Build.sh"];
+#[cfg(target_os = "macos")]
+const BUILD_SCRIPT: &[&str] = &["Mac", "Build.sh"];
+
+#[cfg(target_os = "windows")]
+const RUN_UAT_SCRIPT: &[&str] = &["RunUAT.bat"];
+#[cfg(not(target_os = "windows"))]
+const RUN_UAT_SCRIPT: &[&str] = &["RunUAT.sh"];
+
+/* Returns the engine's build script for this host, under Engine/Build/BatchFiles. */
+pub fn build_script(installation: &UnrealInstallation) -> Result<PathBuf, UnrealError> {
+    batch_file(installation, BUILD_SCRIPT)
+}
+
+/* Returns the engine's AutomationTool script for this host, under Engine/Build/BatchFiles. */
+pub fn run_uat_script(installation: &UnrealInstallation) -> Result<PathBuf, UnrealError> {
+    batch_file(installation, RUN_UAT_SCRIPT)
+}
+
+fn batch_file(
+    installation: &UnrealInstallation,
+    components: &[&str],
+) -> Result<PathBuf, UnrealError> {
+    let path = components
+        .iter()
+        .fold(batch_files_dir(installation), |path, component| {
+            path.join(component)
+        });
+
+    match path.exists() {
+        true => Ok(path),
+        false => Err(UnrealError::ScriptNotFound {
+            path: path.to_string_lossy().to_string(),
+        }),
+    }
+}
+
+fn batch_files_dir(installation: &UnrealInstallation) -> PathBuf {
+    Path::new(&installation.base_path)
+        .join("Engine")
+        .join("Build")
+        .join("BatchFiles")
+}
+
+/* Creates the command that runs a build or automation script. */
+#[cfg(target_os = "windows")]
+pub fn script_command(script: &Path) -> Command {
+    let mut command = Command::new("cmd");
+    command.args(["/C", "call"]).arg(script);
+    command
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn script_command(script: &Path) -> Command {
+    Command::new(script)
+}
+
+/* Creates the command that launches the editor. */
+#[cfg(target_os = "windows")]
+pub fn editor_command(exe_path: &Path) -> Command {
+    let mut command = Command::new("cmd");
+    command.args(["/C", "start"]).arg(exe_path);
+    command
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn editor_command(exe_path: &Path) -> Command {
+    Command::new(exe_path)
+}
+
+/* Returns the command line editor next to the editor executable, falling back to the editor itself. */
+pub fn editor_cmd_executable(exe_path: &Path) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    const CMD_EXECUTABLE: &str = "UnrealEditor-Cmd.exe";
+    #[cfg(not(target_os = "windows"))]
+    const CMD_EXECUTABLE: &str = "UnrealEditor-Cmd";
+
+    exe_path
+        .parent()
+        .map(|dir| dir.join(CMD_EXECUTABLE))
+        .filter(|path| path.exists())
+        .unwrap_or_else(|| exe_path.to_path_buf())
+}
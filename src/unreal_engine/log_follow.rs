@@ -0,0 +1,180 @@
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+    time::Duration,
+};
+
+use crate::unreal_engine::{
+    error::UnrealError,
+    unreal_log::{LogFilter, LogRecord, Verbosity},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Reads the lines appended to a log file, starting over when the file is replaced.
+struct LogTail {
+    path: PathBuf,
+    /// the file being read, the editor moves the last log to a backup on start and writes a new one.
+    identity: Option<FileId>,
+    position: u64,
+    partial: String,
+}
+
+/// Tells two files at the same path apart. The creation time can't, NTFS gives a file replacing another one the
+/// creation time of the replaced file.
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct FileId {
+    volume: u64,
+    index: u64,
+}
+
+impl LogTail {
+    /* Starts at the end of the current file, it belongs to an earlier session. */
+    fn new(path: &Path) -> LogTail {
+        let file = File::open(path).ok();
+
+        LogTail {
+            path: path.to_path_buf(),
+            identity: file.as_ref().and_then(|file| file_id(file).ok()),
+            position: file
+                .and_then(|file| file.metadata().ok())
+                .map(|metadata| metadata.len())
+                .unwrap_or(0),
+            partial: String::new(),
+        }
+    }
+
+    fn read_lines(&mut self) -> Result<Vec<String>, UnrealError> {
+        // The identity and the contents are read through the same handle, the file can't be swapped in between.
+        let Ok(mut file) = File::open(&self.path) else {
+            return Ok(Vec::new());
+        };
+
+        let identity = file_id(&file).ok();
+        let len = file.metadata()?.len();
+
+        // A shorter file is a new one too, where the identity isn't available.
+        if identity != self.identity || len < self.position {
+            self.identity = identity;
+            self.position = 0;
+            self.partial.clear();
+        }
+
+        if len == self.position {
+            return Ok(Vec::new());
+        }
+
+        file.seek(SeekFrom::Start(self.position))?;
+
+        let mut bytes = Vec::new();
+        self.position += file.read_to_end(&mut bytes)? as u64;
+
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+        self.partial.push_str(&String::from_utf8_lossy(bytes));
+
+        let Some(end) = self.partial.rfind('\n') else {
+            return Ok(Vec::new());
+        };
+
+        let complete: String = self.partial.drain(..=end).collect();
+        Ok(complete.lines().map(str::to_owned).collect())
+    }
+
+    /* The last line, when the editor exited without ending it. */
+    fn remainder(&mut self) -> Option<String> {
+        let line = std::mem::take(&mut self.partial);
+        (!line.trim().is_empty()).then_some(line)
+    }
+}
+
+/* Spawns the command and prints the log lines matching the filter until it exits, then returns its exit status.
+The log is looked at before the spawn, so the file the command replaces is told apart from its own. */
+pub fn follow(
+    mut command: Command,
+    log_path: &Path,
+    filter: &LogFilter,
+    color: bool,
+) -> Result<ExitStatus, UnrealError> {
+    let mut tail = LogTail::new(log_path);
+
+    let mut child = command.spawn().map_err(|error| UnrealError::SpawnFailed {
+        program: command.get_program().to_string_lossy().to_string(),
+        error,
+    })?;
+
+    loop {
+        // Checked before reading, so the lines written right before the exit are still printed.
+        let status = child.try_wait()?;
+
+        let mut lines = tail.read_lines()?;
+
+        if status.is_some() {
+            lines.extend(tail.remainder());
+        }
+
+        for line in lines {
+            let record = LogRecord::parse(&line);
+
+            if filter.matches(&record) {
+                print_record(&record, color);
+            }
+        }
+
+        if let Some(status) = status {
+            return Ok(status);
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/* The device and inode of the file. */
+#[cfg(not(target_os = "windows"))]
+fn file_id(file: &File) -> io::Result<FileId> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = file.metadata()?;
+
+    Ok(FileId {
+        volume: metadata.dev(),
+        index: metadata.ino(),
+    })
+}
+
+/* The volume serial number and file index of the file. */
+#[cfg(target_os = "windows")]
+fn file_id(file: &File) -> io::Result<FileId> {
+    use std::os::windows::io::AsRawHandle;
+
+    use windows::Win32::{
+        Foundation::HANDLE,
+        Storage::FileSystem::{BY_HANDLE_FILE_INFORMATION, GetFileInformationByHandle},
+    };
+
+    let mut information = BY_HANDLE_FILE_INFORMATION::default();
+
+    // SAFETY: the handle stays open for the duration of the call.
+    unsafe { GetFileInformationByHandle(HANDLE(file.as_raw_handle()), &mut information) }
+        .map_err(io::Error::other)?;
+
+    Ok(FileId {
+        volume: u64::from(information.dwVolumeSerialNumber),
+        index: (u64::from(information.nFileIndexHigh) << 32) | u64::from(information.nFileIndexLow),
+    })
+}
+
+fn print_record(record: &LogRecord, color: bool) {
+    let code = match record.verbosity {
+        Verbosity::Fatal => Some("\x1b[1;31m"),
+        Verbosity::Error => Some("\x1b[31m"),
+        Verbosity::Warning => Some("\x1b[33m"),
+        _ => None,
+    };
+
+    match code.filter(|_| color) {
+        Some(code) => println!("{code}{record}\x1b[0m"),
+        None => println!("{record}"),
+    }
+}
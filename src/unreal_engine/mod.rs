@@ -1,5 +1,29 @@
+pub mod annotations;
 pub mod args;
+pub mod asset_validation;
+pub mod automation;
+pub mod build_matrix;
+pub mod build_target;
 pub mod command;
+pub mod commandlet;
+pub mod diagnostics;
+pub mod engine_association;
+pub mod engine_index;
+pub mod engine_registry;
 pub mod error;
+pub mod host_platform;
+pub mod log_follow;
+pub mod packaging;
+pub mod process;
+pub mod project_cache;
+pub mod project_descriptor;
+pub mod project_files;
+pub mod python;
+pub mod quarantine;
+pub mod sharding;
 pub mod unreal_installation;
+pub mod unreal_log;
 pub mod unreal_project;
+pub mod version_requirement;
+pub mod warning_baseline;
+pub mod workspace;
@@ -0,0 +1,149 @@
+use std::sync::Mutex;
+
+use crate::unreal_engine::{
+    args::PackageArgs,
+    diagnostics::{self, DiagnosticCollector},
+    error::UnrealError,
+    host_platform, process,
+    unreal_project::UnrealProject,
+};
+
+/// The `AutomationTool exiting with ExitCode=25 (Error_UnknownCookFailure)` line UAT ends with.
+const EXIT_MARKER: &str = "AutomationTool exiting with ExitCode=";
+
+/* Builds, cooks, stages and packages the project through RunUAT BuildCookRun. */
+pub fn run(project: &UnrealProject, args: &PackageArgs) -> Result<(), UnrealError> {
+    let target = match &args.target {
+        Some(target) => Some(project.resolve_target(target)?),
+        None => None,
+    };
+
+    let script = host_platform::run_uat_script(project.engine())?;
+    let configuration = args.configuration.as_str();
+
+    let mut command = host_platform::script_command(&script);
+    command
+        .arg("BuildCookRun")
+        .arg(format!("-project={}", project.path().to_string_lossy()))
+        .arg(format!("-platform={}", args.platform))
+        .arg(format!("-clientconfig={configuration}"))
+        .arg(format!("-serverconfig={configuration}"))
+        .args(["-noP4", "-utf8output", "-unattended", "-stage", "-package"]);
+
+    command.arg(match args.skip_build {
+        true => "-skipbuild",
+        false => "-build",
+    });
+
+    command.arg(match args.skip_cook {
+        true => "-skipcook",
+        false => "-cook",
+    });
+
+    if let Some(target) = &target {
+        command.arg(format!("-target={}", target.name));
+    }
+
+    if !args.maps.is_empty() {
+        command.arg(format!("-map={}", args.maps.join("+")));
+    }
+
+    if args.server {
+        command.arg("-server");
+
+        if let Some(platform) = &args.server_platform {
+            command.arg(format!("-serverplatform={platform}"));
+        }
+    }
+
+    if let Some(dir) = &args.archive_dir {
+        command
+            .arg("-archive")
+            .arg(format!("-archivedirectory={dir}"));
+    }
+
+    if let Some(dir) = &args.staging_dir {
+        command.arg(format!("-stagingdirectory={dir}"));
+    }
+
+    let flags = [
+        (args.client, "-client"),
+        (args.server_only, "-noclient"),
+        (args.pak, "-pak"),
+        (args.iostore, "-iostore"),
+        (args.compressed, "-compressed"),
+        (args.no_debug_info, "-nodebuginfo"),
+        (args.prereqs, "-prereqs"),
+        (args.distribution, "-distribution"),
+    ];
+
+    command.args(flags.iter().filter(|(set, _)| *set).map(|(_, flag)| flag));
+    command.args(&args.extra);
+
+    let collector = DiagnosticCollector::default();
+    let exit_code = Mutex::new(None);
+
+    let observer = |line: &str| {
+        collector.observe(line);
+
+        if let (Some(code), Ok(mut exit_code)) = (parse_exit_code(line), exit_code.lock()) {
+            *exit_code = Some(code);
+        }
+    };
+
+    println!(
+        "\nStarting Project Packaging : {} {}..",
+        args.platform, configuration
+    );
+    let result = process::run_to_completion(command, None, Some(&observer));
+
+    diagnostics::print_summary(&collector.into_diagnostics());
+
+    // UAT's own exit code says which step failed, the script's exit status doesn't always.
+    match exit_code.into_inner().ok().flatten() {
+        Some((0, _)) | None => result,
+        Some((code, reason)) => Err(UnrealError::AutomationToolFailed {
+            reason: reason.unwrap_or_else(|| describe_exit_code(code).to_owned()),
+            code,
+        }),
+    }
+}
+
+/* Parses `AutomationTool exiting with ExitCode=25 (Error_UnknownCookFailure)`. */
+fn parse_exit_code(line: &str) -> Option<(i32, Option<String>)> {
+    let (_, rest) = line.split_once(EXIT_MARKER)?;
+
+    let digits: String = rest
+        .chars()
+        .enumerate()
+        .take_while(|(index, c)| c.is_ascii_digit() || (*index == 0 && *c == '-'))
+        .map(|(_, c)| c)
+        .collect();
+
+    let code = digits.parse().ok()?;
+
+    let reason = rest[digits.len()..]
+        .trim()
+        .strip_prefix('(')
+        .and_then(|reason| reason.split_once(')'))
+        .map(|(reason, _)| reason.to_owned());
+
+    Some((code, reason))
+}
+
+/* The names of AutomationTool's most common exit codes, for the lines that don't carry one. */
+fn describe_exit_code(code: i32) -> &'static str {
+    match code {
+        1 => "Error_Unknown",
+        2 => "Error_Arguments",
+        3 => "Error_UnknownCommand",
+        10 => "Error_SDKNotFound",
+        25 => "Error_UnknownCookFailure",
+        26 => "Error_UnknownDeployFailure",
+        27 => "Error_UnknownBuildFailure",
+        28 => "Error_UnknownPackageFailure",
+        29 => "Error_UnknownLaunchFailure",
+        30 => "Error_StageMissingFile",
+        _ => "unknown exit code",
+    }
+}
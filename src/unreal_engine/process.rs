@@ -0,0 +1,82 @@
+use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+};
+
+use crate::unreal_engine::error::UnrealError;
+
+/* Spawns the command with piped output. */
+pub fn create_child_process(mut command: Command) -> Result<Child, UnrealError> {
+    command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| UnrealError::SpawnFailed {
+            program: command.get_program().to_string_lossy().to_string(),
+            error,
+        })
+}
+
+/// Called with every line the child prints, on both pipes.
+pub type LineObserver<'a> = &'a (dyn Fn(&str) + Sync);
+
+/* Spawns the command, echoes its output and fails on a non-zero exit status. */
+pub fn run_to_completion(
+    command: Command,
+    label: Option<&str>,
+    observer: Option<LineObserver>,
+) -> Result<(), UnrealError> {
+    let mut child = create_child_process(command)?;
+
+    monitor_output(&mut child, label, observer)?;
+
+    let status = child.wait()?;
+
+    if !status.success() {
+        return Err(UnrealError::FailedExitStatus { status });
+    }
+
+    Ok(())
+}
+
+/* Echoes the child's output, prefixing each line with the label so concurrent children can be told apart. */
+pub fn monitor_output(
+    child: &mut Child,
+    label: Option<&str>,
+    observer: Option<LineObserver>,
+) -> Result<(), UnrealError> {
+    let stdout = BufReader::new(child.stdout.take().ok_or(UnrealError::OutputNotCaptured)?);
+    let stderr = BufReader::new(child.stderr.take().ok_or(UnrealError::OutputNotCaptured)?);
+
+    let out_lines = stdout.lines();
+    let err_lines = stderr.lines();
+
+    // A dedicated thread per pipe, a rayon scope deadlocks on single core agents when both pipes block.
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            for line in out_lines.into_iter().map_while(Result::ok) {
+                if let Some(observer) = observer {
+                    observer(&line);
+                }
+
+                match label {
+                    Some(label) => println!("[{label}] {line}"),
+                    None => println!("{line}"),
+                }
+            }
+        });
+
+        for line in err_lines.into_iter().map_while(Result::ok) {
+            if let Some(observer) = observer {
+                observer(&line);
+            }
+
+            match label {
+                Some(label) => eprintln!("[{label}] {line}"),
+                None => eprintln!("{line}"),
+            }
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    unreal_engine::{command::CacheCommand, error::UnrealError, unreal_project::UnrealProject},
+    utility::{hash, search::SearchOptions},
+};
+
+/// Bumped whenever the cached layout changes, older caches are resolved again.
+const CACHE_VERSION: u32 = 1;
+const CACHE_FILE: &str = "project.json";
+
+/// The resolved project, with what it was resolved from. Saving borrows the project, loading owns it.
+#[derive(Serialize, Deserialize)]
+pub struct CachedProject<P = UnrealProject> {
+    version: u32,
+    fingerprint: ProjectFingerprint,
+    project: P,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct ProjectFingerprint {
+    /// the .uproject's modification time, in nanoseconds since the unix epoch.
+    modified: u64,
+    /// the FNV-1a hash of the .uproject, catches the edits within the file system's mtime resolution.
+    hash: String,
+    /// the --engine-version or --ue-*-version the engine was picked with, if any.
+    engine_override: Option<String>,
+}
+
+/// Why a cached project can't be used.
+#[derive(PartialEq, Debug)]
+pub enum CacheMiss {
+    Missing,
+    Unreadable { reason: String },
+    Outdated { version: u32 },
+    UprojectChanged,
+    EngineOverrideChanged,
+    EngineMissing { path: String },
+}
+
+/* The cache lives next to the project's build products, cleaning Intermediate clears it too. */
+pub fn cache_path(uproject_path: &Path) -> PathBuf {
+    uproject_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("Intermediate")
+        .join("CiHelper")
+        .join(CACHE_FILE)
+}
+
+/* Loads the cached project of the .uproject, as long as neither it nor the engine changed since. */
+pub fn load(
+    uproject_path: &Path,
+    engine_override: Option<&str>,
+) -> Result<UnrealProject, CacheMiss> {
+    let cached = read(&cache_path(uproject_path))?;
+    cached.validate(uproject_path, engine_override)?;
+
+    Ok(cached.project)
+}
+
+/* Writes the project's cache, returning its path. */
+pub fn save(
+    project: &UnrealProject,
+    engine_override: Option<&str>,
+) -> Result<PathBuf, UnrealError> {
+    let cached = CachedProject {
+        version: CACHE_VERSION,
+        fingerprint: ProjectFingerprint::of(project.path(), engine_override)?,
+        project,
+    };
+
+    let path = cache_path(project.path());
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let file = std::fs::File::create(&path)?;
+    serde_json::to_writer_pretty(file, &cached)?;
+
+    Ok(path)
+}
+
+pub fn process_cache_command(
+    command: CacheCommand,
+    options: &SearchOptions,
+) -> Result<(), UnrealError> {
+    let uproject_path = UnrealProject::find_uproject(options)?;
+
+    let path = cache_path(&uproject_path);
+
+    match command {
+        CacheCommand::Show { json } => {
+            let cached = match read(&path) {
+                Ok(cached) => cached,
+                Err(miss) => {
+                    println!("No usable cached project at {} : {miss}", path.display());
+                    return Ok(());
+                }
+            };
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&cached)?);
+                return Ok(());
+            }
+
+            let engine_override = options
+                .version_override()
+                .map(|override_| override_.to_string());
+            let engine = cached.project.engine();
+
+            println!("Cache : {}", path.display());
+            println!("Project : {}", cached.project.path().display());
+            println!(
+                "Engine : {} ({:?}) : {}",
+                engine.version, engine.kind, engine.base_path
+            );
+
+            if let Some(engine_override) = &cached.fingerprint.engine_override {
+                println!("Engine Override : {engine_override}");
+            }
+
+            match cached.validate(&uproject_path, engine_override.as_deref()) {
+                Ok(()) => println!("State : valid"),
+                Err(miss) => println!("State : stale, {miss}"),
+            }
+
+            Ok(())
+        }
+        CacheCommand::Clear => {
+            match std::fs::remove_file(&path) {
+                Ok(()) => println!("Removed the cached project : {}", path.display()),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    println!("No cached project at {}", path.display())
+                }
+                Err(err) => return Err(err.into()),
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/* Reads a cache file, rejecting the ones written by another cache version before their contents are parsed. */
+fn read(path: &Path) -> Result<CachedProject, CacheMiss> {
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Err(CacheMiss::Missing),
+        Err(err) => {
+            return Err(CacheMiss::Unreadable {
+                reason: err.to_string(),
+            });
+        }
+    };
+
+    #[derive(Deserialize)]
+    struct Header {
+        version: u32,
+    }
+
+    let header: Header =
+        serde_json::from_slice(&contents).map_err(|err| CacheMiss::Unreadable {
+            reason: err.to_string(),
+        })?;
+
+    if header.version != CACHE_VERSION {
+        return Err(CacheMiss::Outdated {
+            version: header.version,
+        });
+    }
+
+    serde_json::from_slice(&contents).map_err(|err| CacheMiss::Unreadable {
+        reason: err.to_string(),
+    })
+}
+
+impl CachedProject {
+    fn validate(
+        &self,
+        uproject_path: &Path,
+        engine_override: Option<&str>,
+    ) -> Result<(), CacheMiss> {
+        let fingerprint =
+            ProjectFingerprint::of(uproject_path, engine_override).map_err(|err| {
+                CacheMiss::Unreadable {
+                    reason: err.to_string(),
+                }
+            })?;
+
+        if fingerprint.engine_override != self.fingerprint.engine_override {
+            return Err(CacheMiss::EngineOverrideChanged);
+        }
+
+        if fingerprint != self.fingerprint || self.project.path() != uproject_path {
+            return Err(CacheMiss::UprojectChanged);
+        }
+
+        let engine = self.project.engine();
+
+        for path in [&engine.exe_path, &engine.base_path] {
+            if !Path::new(path).exists() {
+                return Err(CacheMiss::EngineMissing { path: path.clone() });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ProjectFingerprint {
+    fn of(uproject_path: &Path, engine_override: Option<&str>) -> std::io::Result<Self> {
+        let modified = std::fs::metadata(uproject_path)?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64);
+
+        let contents = std::fs::read(uproject_path)?;
+        let hash = hash::fnv1a(&[&String::from_utf8_lossy(&contents)]);
+
+        Ok(ProjectFingerprint {
+            modified,
+            hash: format!("{hash:016x}"),
+            engine_override: engine_override.map(str::to_owned),
+        })
+    }
+}
+
+impl Display for CacheMiss {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheMiss::Missing => write!(f, "there is no cache"),
+            CacheMiss::Unreadable { reason } => write!(f, "the cache is unreadable, {reason}"),
+            CacheMiss::Outdated { version } => write!(
+                f,
+                "the cache has version {version}, the current one is {CACHE_VERSION}"
+            ),
+            CacheMiss::UprojectChanged => write!(f, "the .uproject changed since it was cached"),
+            CacheMiss::EngineOverrideChanged => {
+                write!(f, "the engine version override changed")
+            }
+            CacheMiss::EngineMissing { path } => write!(f, "the engine is gone : {path}"),
+        }
+    }
+}
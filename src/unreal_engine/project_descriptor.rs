@@ -0,0 +1,367 @@
+use std::{io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::unreal_engine::{
+    args::InfoArgs, engine_association::EngineAssociation, error::UnrealError,
+    unreal_installation::UnrealInstallation, unreal_project::UnrealProject,
+};
+
+/// The contents of a .uproject file. The fields this model doesn't know are kept in `extra`,
+/// `to_json` writes them back in the file's original order.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProjectDescriptor {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_version: Option<u32>,
+    /// missing for projects inside the engine's source tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_association: Option<EngineAssociation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<ModuleDescriptor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PluginReference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub target_platforms: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_plugin_directories: Vec<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    /// the file as it was read, its key order is the one written back.
+    #[serde(skip)]
+    source: Map<String, Value>,
+}
+
+/// A C++ module of the project.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ModuleDescriptor {
+    pub name: String,
+    /// where the module is loaded, Ex : "Runtime", "Editor" or "UncookedOnly".
+    #[serde(rename = "Type")]
+    pub module_type: String,
+    /// when the module is loaded, "Default" when missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loading_phase: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A plugin the project enables or disables.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PluginReference {
+    pub name: String,
+    /// kept missing when the descriptor omits it, so writing the descriptor back doesn't add it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// the only platforms the plugin is enabled on, all of them when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platform_allow_list: Vec<String>,
+    /// the platforms the plugin is disabled on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platform_deny_list: Vec<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The output of `unreal info --json`.
+#[derive(Serialize)]
+struct ProjectInfo<'a> {
+    name: &'a str,
+    path: &'a Path,
+    descriptor: Value,
+    engine: &'a UnrealInstallation,
+}
+
+impl ProjectDescriptor {
+    pub fn engine_association(&self) -> EngineAssociation {
+        self.engine_association
+            .clone()
+            .unwrap_or(EngineAssociation::InTree)
+    }
+
+    /* Parses a .uproject, remembering its layout for `to_json`. */
+    pub fn from_slice(contents: &[u8]) -> serde_json::Result<ProjectDescriptor> {
+        let source: Map<String, Value> = serde_json::from_slice(contents)?;
+
+        let mut descriptor: ProjectDescriptor =
+            serde_json::from_value(Value::Object(source.clone()))?;
+        descriptor.source = source;
+
+        Ok(descriptor)
+    }
+
+    /* The descriptor as JSON, with the keys in the order of the file it was read from. Serde alone would write the
+    known fields first and the unknown ones after them. */
+    pub fn to_json(&self) -> serde_json::Result<Value> {
+        let written = serde_json::to_value(self)?;
+
+        Ok(in_source_order(
+            &Value::Object(self.source.clone()),
+            written,
+        ))
+    }
+
+    /* Whether the descriptor enables the plugin, engine plugins enabled by default aren't listed and count as disabled. */
+    pub fn is_plugin_enabled(&self, plugin: &str) -> bool {
+        self.plugins.iter().any(|reference| {
+            reference.name.eq_ignore_ascii_case(plugin) && reference.enabled == Some(true)
+        })
+    }
+}
+
+impl TryFrom<&Path> for ProjectDescriptor {
+    type Error = std::io::Error;
+
+    fn try_from(value: &Path) -> Result<Self, Self::Error> {
+        let file = std::fs::read(value)?;
+
+        let result = Self::from_slice(&file);
+
+        match result {
+            Ok(descriptor) => Ok(descriptor),
+            Err(err) => Err(io::Error::other(err)),
+        }
+    }
+}
+
+/* Orders the written value's keys like the source's, the keys the source doesn't have go last. Objects in arrays are
+matched by position, like the modules and plugins lists. */
+fn in_source_order(source: &Value, written: Value) -> Value {
+    match (source, written) {
+        (Value::Object(source), Value::Object(mut written)) => {
+            let mut ordered = Map::new();
+
+            for (key, source_value) in source {
+                if let Some(value) = written.shift_remove(key) {
+                    ordered.insert(key.clone(), in_source_order(source_value, value));
+                }
+            }
+
+            ordered.extend(written);
+            Value::Object(ordered)
+        }
+        (Value::Array(source), Value::Array(written)) => Value::Array(
+            written
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| match source.get(index) {
+                    Some(source_value) => in_source_order(source_value, value),
+                    None => value,
+                })
+                .collect(),
+        ),
+        (_, written) => written,
+    }
+}
+
+/* Prints the project's descriptor together with the engine it resolved to. */
+pub fn print_info(project: &UnrealProject, args: &InfoArgs) -> Result<(), UnrealError> {
+    let descriptor = ProjectDescriptor::try_from(project.path())?;
+    let engine = project.engine();
+
+    if args.json {
+        let info = ProjectInfo {
+            name: project.name(),
+            path: project.path(),
+            descriptor: descriptor.to_json()?,
+            engine,
+        };
+
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
+
+    println!("Project : {}", project.name());
+    println!("Path : {}", project.path().display());
+
+    match descriptor.engine_association() {
+        EngineAssociation::InTree => println!("Engine Association : (in tree)"),
+        association => println!("Engine Association : {association}"),
+    }
+
+    println!(
+        "Engine : {} ({:?}) : {}",
+        engine.version, engine.kind, engine.base_path
+    );
+    println!("    Executable : {}", engine.exe_path);
+
+    if let Some(file_version) = descriptor.file_version {
+        println!("File Version : {file_version}");
+    }
+
+    if let Some(category) = &descriptor.category {
+        println!("Category : {category}");
+    }
+
+    if let Some(description) = &descriptor.description {
+        println!("Description : {description}");
+    }
+
+    if !descriptor.modules.is_empty() {
+        println!("Modules :");
+    }
+
+    for module in &descriptor.modules {
+        println!(
+            "    {} ({}, {})",
+            module.name,
+            module.module_type,
+            module.loading_phase.as_deref().unwrap_or("Default")
+        );
+    }
+
+    if !descriptor.plugins.is_empty() {
+        println!("Plugins :");
+    }
+
+    for plugin in &descriptor.plugins {
+        let mut line = format!(
+            "    {} : {}",
+            plugin.name,
+            match plugin.enabled {
+                Some(true) => "enabled",
+                Some(false) | None => "disabled",
+            }
+        );
+
+        if !plugin.platform_allow_list.is_empty() {
+            line.push_str(&format!(
+                ", only on {}",
+                plugin.platform_allow_list.join(", ")
+            ));
+        }
+
+        if !plugin.platform_deny_list.is_empty() {
+            line.push_str(&format!(
+                ", not on {}",
+                plugin.platform_deny_list.join(", ")
+            ));
+        }
+
+        println!("{line}");
+    }
+
+    if !descriptor.target_platforms.is_empty() {
+        println!(
+            "Target Platforms : {}",
+            descriptor.target_platforms.join(", ")
+        );
+    }
+
+    for dir in &descriptor.additional_plugin_directories {
+        println!("Additional Plugin Directory : {dir}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Laid out the way the editor writes it, with unknown keys between the known ones.
+    const UPROJECT: &str = r#"{
+	"FileVersion": 3,
+	"EngineAssociation": "5.4",
+	"Category": "",
+	"Description": "",
+	"DisableEnginePluginsByDefault": true,
+	"Modules": [
+		{
+			"Name": "Demo",
+			"Type": "Runtime",
+			"LoadingPhase": "Default",
+			"AdditionalDependencies": [
+				"Engine"
+			]
+		}
+	],
+	"Plugins": [
+		{
+			"Name": "ModelingToolsEditorMode",
+			"Enabled": true,
+			"TargetAllowList": [
+				"Editor"
+			]
+		},
+		{
+			"Name": "OnlineSubsystemSteam",
+			"MarketplaceURL": "com.epicgames.launcher://ue/marketplace/content/0",
+			"PlatformAllowList": [
+				"Win64"
+			]
+		}
+	],
+	"AdditionalPluginDirectories": [
+		"../SharedPlugins"
+	],
+	"TargetPlatforms": [
+		"Linux"
+	]
+}"#;
+
+    #[test]
+    fn round_trip_keeps_the_key_order() {
+        let descriptor = ProjectDescriptor::from_slice(UPROJECT.as_bytes()).unwrap();
+
+        let written = serde_json::to_string(&descriptor.to_json().unwrap()).unwrap();
+        let original =
+            serde_json::to_string(&serde_json::from_str::<Value>(UPROJECT).unwrap()).unwrap();
+
+        assert_eq!(written, original);
+        assert!(descriptor.is_plugin_enabled("ModelingToolsEditorMode"));
+        assert!(!descriptor.is_plugin_enabled("OnlineSubsystemSteam"));
+    }
+
+    #[test]
+    fn changed_values_are_written_in_place() {
+        let mut descriptor = ProjectDescriptor::from_slice(UPROJECT.as_bytes()).unwrap();
+        descriptor.plugins[1].enabled = Some(false);
+        descriptor.target_platforms.push(String::from("Win64"));
+
+        let written = descriptor.to_json().unwrap();
+
+        let keys: Vec<&str> = written
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "FileVersion",
+                "EngineAssociation",
+                "Category",
+                "Description",
+                "DisableEnginePluginsByDefault",
+                "Modules",
+                "Plugins",
+                "AdditionalPluginDirectories",
+                "TargetPlatforms"
+            ]
+        );
+
+        let plugin: Vec<&str> = written["Plugins"][1]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            plugin,
+            ["Name", "MarketplaceURL", "PlatformAllowList", "Enabled"]
+        );
+        assert_eq!(
+            written["TargetPlatforms"],
+            serde_json::json!(["Linux", "Win64"])
+        );
+    }
+}
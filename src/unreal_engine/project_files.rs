@@ -0,0 +1,395 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    unreal_engine::{
+        args::GenerateArgs, error::UnrealError, host_platform, process,
+        unreal_installation::InstallationKind, unreal_project::UnrealProject,
+    },
+    utility::{path_utility, repository},
+};
+
+#[derive(ValueEnum, Serialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdeFormat {
+    /// a Visual Studio 2022 solution.
+    VisualStudio,
+    VsCode,
+    Rider,
+    Xcode,
+    Make,
+    Cmake,
+}
+
+const DATABASE_FILE: &str = "compile_commands.json";
+
+/// The flags whose value is glued to them and names a path, the longest first so `-isystem` isn't read as `-i`.
+const PATH_FLAGS: &[&str] = &["-isystem", "-iquote", "-include", "-I", "-o", "@"];
+
+impl IdeFormat {
+    /* The UnrealBuildTool switch selecting the format. */
+    fn ubt_flag(&self) -> &'static str {
+        match self {
+            IdeFormat::VisualStudio => "-2022",
+            IdeFormat::VsCode => "-VSCode",
+            IdeFormat::Rider => "-Rider",
+            IdeFormat::Xcode => "-XCodeProjectFiles",
+            IdeFormat::Make => "-Makefile",
+            IdeFormat::Cmake => "-CMakefile",
+        }
+    }
+}
+
+pub fn generate(project: &UnrealProject, args: &GenerateArgs) -> Result<(), UnrealError> {
+    match args.clang_database {
+        true => generate_clang_database(project, args),
+        false => generate_project_files(project, args.format),
+    }
+}
+
+/* Runs UBT's -ProjectFiles mode, the engine's sources are only included for source builds. */
+fn generate_project_files(
+    project: &UnrealProject,
+    format: Option<IdeFormat>,
+) -> Result<(), UnrealError> {
+    let script = host_platform::build_script(project.engine())?;
+
+    let mut command = host_platform::script_command(&script);
+    command
+        .arg("-ProjectFiles")
+        .arg(format!("-project={}", project.path().to_string_lossy()))
+        .arg("-game");
+
+    if project.engine().kind == InstallationKind::Source {
+        command.arg("-engine");
+    }
+
+    if let Some(format) = format {
+        command.arg(format.ubt_flag());
+    }
+
+    println!("\nGenerating Project Files..");
+    process::run_to_completion(command, None, None)
+}
+
+/* Runs UBT's GenerateClangDatabase mode for the target, then optionally makes the paths repository relative. */
+fn generate_clang_database(
+    project: &UnrealProject,
+    args: &GenerateArgs,
+) -> Result<(), UnrealError> {
+    let target = project.resolve_target(&args.target)?;
+    let script = host_platform::build_script(project.engine())?;
+
+    let output_dir = match &args.output_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => project.project_dir().to_path_buf(),
+    };
+
+    std::fs::create_dir_all(&output_dir)?;
+    let database_path = output_dir.join(DATABASE_FILE);
+
+    let mut command = host_platform::script_command(&script);
+    command
+        .args([
+            target.name.as_str(),
+            args.platform.as_str(),
+            args.configuration.as_str(),
+        ])
+        .arg(format!("-project={}", project.path().to_string_lossy()))
+        .arg("-mode=GenerateClangDatabase")
+        .arg(format!("-OutputDir={}", output_dir.to_string_lossy()));
+
+    println!(
+        "\nGenerating {DATABASE_FILE} : {} {} {}..",
+        target.name,
+        args.configuration.as_str(),
+        args.platform
+    );
+    process::run_to_completion(command, None, None)?;
+
+    // Older engines ignore -OutputDir and write the database to their root.
+    if !database_path.exists() {
+        let engine_database = Path::new(&project.engine().base_path).join(DATABASE_FILE);
+
+        if !engine_database.exists() {
+            return Err(UnrealError::ClangDatabaseNotFound {
+                path: database_path.to_string_lossy().to_string(),
+            });
+        }
+
+        std::fs::copy(&engine_database, &database_path)?;
+    }
+
+    if args.relative {
+        let root = repository::repository_root(project.project_dir())
+            .unwrap_or_else(|| project.project_dir().to_path_buf());
+
+        make_relative(&database_path, &root, project.project_dir())?;
+        println!(
+            "The paths are relative to the repository, run the clang tools from its root : {}",
+            root.to_string_lossy()
+        );
+    }
+
+    println!("Compile commands : {}", database_path.to_string_lossy());
+    Ok(())
+}
+
+/* Rewrites the database so it doesn't depend on where the repository is checked out. Each entry's directory becomes
+relative to the repository root and its paths relative to that directory, the way the compilers read them. Paths
+outside of the repository, like an installed engine, are kept. `directory` stays absolute when it lies outside of the
+repository, the paths into the repository are then relative to it and expect the same layout on every agent. */
+fn make_relative(database_path: &Path, root: &Path, project_dir: &Path) -> Result<(), UnrealError> {
+    let contents = std::fs::read(database_path)?;
+    let mut database: Value = serde_json::from_slice(&contents)?;
+
+    let roots = Roots::new(root, project_dir);
+
+    if let Value::Array(entries) = &mut database {
+        for entry in entries {
+            make_absolute(entry);
+            relative_entry(entry, &roots);
+        }
+    }
+
+    let file = std::fs::File::create(database_path)?;
+    serde_json::to_writer_pretty(file, &database)?;
+    Ok(())
+}
+
+/// The repository root, canonical, and the other spellings the database may use for it, like a symlinked checkout.
+struct Roots {
+    canonical: PathBuf,
+    aliases: Vec<PathBuf>,
+}
+
+impl Roots {
+    /* The project directory wasn't canonicalized, the ancestor of it that is the root is the checkout's own spelling. */
+    fn new(root: &Path, project_dir: &Path) -> Roots {
+        let aliases = project_dir
+            .ancestors()
+            .filter(|dir| {
+                dir.canonicalize()
+                    .is_ok_and(|canonical| path_utility::strip_verbatim(canonical) == root)
+            })
+            .map(Path::to_path_buf)
+            .filter(|dir| dir != root)
+            .collect();
+
+        Roots {
+            canonical: root.to_path_buf(),
+            aliases,
+        }
+    }
+
+    /* The path under the canonical root, None when it lies outside of the repository. */
+    fn rebase(&self, path: &Path) -> Option<PathBuf> {
+        std::iter::once(&self.canonical)
+            .chain(&self.aliases)
+            .find_map(|root| path.strip_prefix(root).ok())
+            .map(|relative| self.canonical.join(relative))
+    }
+}
+
+/* Resolves the entry's file, output and the path arguments of its command against its directory. The command string
+is split into `arguments`, so the quoted paths containing spaces stay whole. */
+fn make_absolute(entry: &mut Value) {
+    let Some(directory) = entry
+        .get("directory")
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+    else {
+        return;
+    };
+
+    if let Value::Object(object) = entry
+        && let Some(Value::String(command)) = object.remove("command")
+    {
+        let arguments = split_command(&command)
+            .into_iter()
+            .map(Value::String)
+            .collect();
+        object.insert(String::from("arguments"), Value::Array(arguments));
+    }
+
+    map_paths(entry, |path| {
+        if Path::new(path).is_absolute() {
+            return None;
+        }
+
+        let absolute = path_utility::join_normalized(&directory, path);
+        absolute.exists().then_some(absolute)
+    });
+
+    for key in ["file", "output"] {
+        if let Some(Value::String(path)) = entry.get_mut(key)
+            && Path::new(path.as_str()).is_relative()
+        {
+            *path = path_utility::join_normalized(&directory, path.as_str())
+                .to_string_lossy()
+                .to_string();
+        }
+    }
+}
+
+/* Makes the absolute paths into the repository relative to the entry's directory, then the directory relative to
+the root. */
+fn relative_entry(entry: &mut Value, roots: &Roots) {
+    let Some(directory) = entry
+        .get("directory")
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+    else {
+        return;
+    };
+
+    let base = roots.rebase(&directory).unwrap_or(directory);
+
+    let relative = |path: &str| {
+        let path = roots.rebase(Path::new(path))?;
+        path_utility::relative_to(&base, path)
+    };
+
+    map_paths(entry, relative);
+
+    for key in ["file", "output"] {
+        if let Some(Value::String(path)) = entry.get_mut(key)
+            && let Some(relative) = relative(path)
+        {
+            *path = relative.to_string_lossy().to_string();
+        }
+    }
+
+    if base.starts_with(&roots.canonical)
+        && let Some(relative) = path_utility::relative_to(&roots.canonical, &base)
+    {
+        entry["directory"] = Value::String(relative.to_string_lossy().to_string());
+    }
+}
+
+/* Rewrites the path of every argument naming one, Ex : `-I../Plugins` or `@"Module.rsp"`. The arguments the function
+returns None for are kept. */
+fn map_paths(entry: &mut Value, map: impl Fn(&str) -> Option<PathBuf>) {
+    let Some(Value::Array(arguments)) = entry.get_mut("arguments") else {
+        return;
+    };
+
+    for argument in arguments {
+        let Value::String(argument) = argument else {
+            continue;
+        };
+
+        let (flag, value) = PATH_FLAGS
+            .iter()
+            .find_map(|flag| Some((*flag, argument.strip_prefix(flag)?)))
+            .unwrap_or(("", argument.as_str()));
+
+        let quoted = value.len() >= 2 && value.starts_with('"') && value.ends_with('"');
+        let path = match quoted {
+            true => &value[1..value.len() - 1],
+            false => value,
+        };
+
+        if path.is_empty() || path.starts_with('-') {
+            continue;
+        }
+
+        if let Some(mapped) = map(path) {
+            let quote = if quoted { "\"" } else { "" };
+            *argument = format!("{flag}{quote}{}{quote}", mapped.to_string_lossy());
+        }
+    }
+}
+
+/* Splits a command line the way a shell would, double and single quotes group the spaces they contain. Backslashes
+are kept as they are, they separate the directories of Windows paths. */
+fn split_command(command: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut argument = String::new();
+    let mut quote = None;
+    let mut started = false;
+
+    for character in command.chars() {
+        match (quote, character) {
+            (Some(open), character) if character == open => quote = None,
+            (Some(_), character) => argument.push(character),
+            (None, '"' | '\'') => {
+                quote = Some(character);
+                started = true;
+            }
+            (None, character) if character.is_whitespace() => {
+                if started {
+                    arguments.push(std::mem::take(&mut argument));
+                    started = false;
+                }
+            }
+            (None, character) => {
+                argument.push(character);
+                started = true;
+            }
+        }
+    }
+
+    if started {
+        arguments.push(argument);
+    }
+
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_commands_like_a_shell() {
+        let cases: [(&str, &[&str]); 4] = [
+            ("clang++ -c Main.cpp", &["clang++", "-c", "Main.cpp"]),
+            (
+                r#"clang++ -I"C:/Program Files/Epic" -c  Main.cpp"#,
+                &["clang++", "-IC:/Program Files/Epic", "-c", "Main.cpp"],
+            ),
+            (
+                r"cl.exe 'D:\Repo\With Space\Main.cpp' /Fo:D:\Repo\Main.obj",
+                &[
+                    "cl.exe",
+                    r"D:\Repo\With Space\Main.cpp",
+                    r"/Fo:D:\Repo\Main.obj",
+                ],
+            ),
+            (r#"clang++ -DNAME="""#, &["clang++", "-DNAME="]),
+        ];
+
+        for (command, expected) in cases {
+            assert_eq!(split_command(command), expected, "{command}");
+        }
+    }
+
+    #[test]
+    fn entries_become_relative_to_the_root() {
+        let roots = Roots {
+            canonical: PathBuf::from("/repo"),
+            aliases: vec![PathBuf::from("/home/agent/repo")],
+        };
+
+        let mut entry = serde_json::json!({
+            "directory": "/home/agent/repo/Game",
+            "file": "/repo/Game/Source/Main.cpp",
+            "arguments": ["clang++", "-I/repo/Plugins/Inc", "-I/opt/UE/Engine/Source", "-IMissing"]
+        });
+
+        relative_entry(&mut entry, &roots);
+
+        assert_eq!(
+            entry,
+            serde_json::json!({
+                "directory": "Game",
+                "file": "Source/Main.cpp",
+                "arguments": ["clang++", "-I../Plugins/Inc", "-I/opt/UE/Engine/Source", "-IMissing"]
+            })
+        );
+    }
+}
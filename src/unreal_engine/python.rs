@@ -0,0 +1,139 @@
+use std::{path::Path, process::Command, sync::Mutex};
+
+use crate::{
+    unreal_engine::{
+        args::PythonArgs,
+        error::UnrealError,
+        host_platform, process,
+        unreal_log::{LogRecord, Verbosity},
+        unreal_project::UnrealProject,
+    },
+    utility::path_utility,
+};
+
+const PYTHON_PLUGIN: &str = "PythonScriptPlugin";
+
+/// Logged by the wrapper once the script returns, the editor's own exit code doesn't carry the script's.
+const EXIT_SENTINEL: &str = "CI_HELPER_PYTHON_EXIT=";
+
+/// Runs the script as `__main__` with its arguments, logs its exit code and closes the editor.
+const WRAPPER: &str = r#"import runpy
+import sys
+import traceback
+
+import unreal
+
+sys.argv = {argv}
+code = 0
+
+try:
+    runpy.run_path(sys.argv[0], run_name="__main__")
+except SystemExit as exit:
+    code = exit.code if isinstance(exit.code, int) else (0 if exit.code is None else 1)
+except BaseException:
+    traceback.print_exc()
+    code = 1
+
+unreal.log("{sentinel}{}".format(code))
+unreal.SystemLibrary.quit_editor()
+"#;
+
+/* Runs a Python script inside the headless editor and fails with the script's exit code. */
+pub fn run(project: &UnrealProject, args: &PythonArgs) -> Result<(), UnrealError> {
+    if !project.is_plugin_enabled(PYTHON_PLUGIN)? {
+        return Err(UnrealError::PluginNotEnabled {
+            plugin: PYTHON_PLUGIN.to_owned(),
+            project: project.path().to_string_lossy().to_string(),
+        });
+    }
+
+    let script = Path::new(&args.script);
+    let script = script
+        .canonicalize()
+        .map(path_utility::strip_verbatim)
+        .map_err(|_| UnrealError::PythonScriptNotFound {
+            script: args.script.clone(),
+        })?;
+
+    let mut argv = vec![script.to_string_lossy().to_string()];
+    argv.extend(args.args.iter().cloned());
+
+    // A json list of strings is also a valid Python list literal.
+    let wrapper = WRAPPER
+        .replace("{argv}", &serde_json::to_string(&argv)?)
+        .replace("{sentinel}", EXIT_SENTINEL);
+
+    let wrapper_dir = project.project_dir().join("Saved").join("CiHelper");
+    std::fs::create_dir_all(&wrapper_dir)?;
+
+    let wrapper_path = wrapper_dir.join("run_python.py");
+    std::fs::write(&wrapper_path, wrapper)?;
+
+    let exe_path = host_platform::editor_cmd_executable(Path::new(&project.engine().exe_path));
+
+    let mut command = Command::new(exe_path);
+    command
+        .arg(project.path())
+        .arg(format!(
+            "-ExecutePythonScript={}",
+            wrapper_path.to_string_lossy()
+        ))
+        .args([
+            "-unattended",
+            "-nullrhi",
+            "-nosplash",
+            "-nosound",
+            "-stdout",
+            "-FullStdOutLogOutput",
+        ]);
+
+    let python_log = Mutex::new(Vec::new());
+    let exit_code = Mutex::new(None);
+
+    let observer = |line: &str| {
+        let record = LogRecord::parse(line);
+
+        if !record.is_category("LogPython") {
+            return;
+        }
+
+        if let (Some(code), Ok(mut exit_code)) = (parse_sentinel(&record.message), exit_code.lock())
+        {
+            *exit_code = Some(code);
+        } else if let Ok(mut python_log) = python_log.lock() {
+            python_log.push(match record.verbosity {
+                Verbosity::Log => record.message,
+                verbosity => format!("{verbosity:?}: {}", record.message),
+            });
+        }
+    };
+
+    println!("\nRunning Python Script : {}..", args.script);
+    let editor_result = process::run_to_completion(command, None, Some(&observer));
+
+    let python_log = python_log.into_inner().unwrap_or_default();
+
+    if let Some(path) = &args.log {
+        std::fs::write(path, python_log.join("\n") + "\n")?;
+    }
+
+    println!("\nPython : {} line(s) logged.", python_log.len());
+
+    match exit_code.into_inner().ok().flatten() {
+        Some(0) => Ok(()),
+        Some(code) => Err(UnrealError::PythonScriptFailed {
+            script: args.script.clone(),
+            code,
+        }),
+        // The editor crashed or quit before the script returned.
+        None => editor_result.and(Err(UnrealError::PythonScriptUnfinished {
+            script: args.script.clone(),
+        })),
+    }
+}
+
+/* Parses the `CI_HELPER_PYTHON_EXIT=1` message the wrapper logs. */
+fn parse_sentinel(message: &str) -> Option<i32> {
+    let (_, code) = message.split_once(EXIT_SENTINEL)?;
+    code.trim().parse().ok()
+}
@@ -0,0 +1,152 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+use toml::value::Datetime;
+
+use crate::{
+    unreal_engine::{error::UnrealError, unreal_project::UnrealProject},
+    utility::{
+        junit::{TestOutcome, TestSuite},
+        repository,
+    },
+};
+
+/// The quarantine file, Ex :
+/// ```toml
+/// [[tests]]
+/// name = "Project.Gameplay.Jump"
+/// owner = "jane"
+/// expires = 2026-12-31
+/// reason = "times out on the build agents"
+/// ```
+#[derive(Deserialize, Default)]
+pub struct Quarantine {
+    #[serde(default)]
+    pub tests: Vec<QuarantinedTest>,
+}
+
+#[derive(Deserialize)]
+pub struct QuarantinedTest {
+    /// the full test path.
+    pub name: String,
+    pub owner: String,
+    /// the test fails the job again from that day on.
+    pub expires: Datetime,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl Quarantine {
+    /* Reads the quarantine file, `.ci_helper/quarantine.toml` at the repository root when none is given. */
+    pub fn load(project: &UnrealProject, path: Option<&str>) -> Result<Quarantine, UnrealError> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let root = repository::repository_root(project.project_dir())
+                    .unwrap_or_else(|| project.project_dir().to_path_buf());
+
+                let path = root.join(".ci_helper").join("quarantine.toml");
+
+                if !path.exists() {
+                    return Ok(Quarantine::default());
+                }
+
+                path
+            }
+        };
+
+        Quarantine::from_file(&path)
+    }
+
+    fn from_file(path: &Path) -> Result<Quarantine, UnrealError> {
+        let contents = std::fs::read_to_string(path)?;
+
+        toml::from_str(&contents).map_err(|err| UnrealError::InvalidQuarantine {
+            reason: err.to_string(),
+        })
+    }
+
+    /* The quarantine entry of the test, if it hasn't expired. */
+    pub fn find(&self, test: &str) -> Option<&QuarantinedTest> {
+        self.tests
+            .iter()
+            .find(|entry| entry.name == test && !entry.is_expired())
+    }
+
+    /* Reports the failures of quarantined tests as skipped, returns how many were. */
+    pub fn apply(&self, suite: &mut TestSuite) -> usize {
+        for entry in self.tests.iter().filter(|entry| entry.is_expired()) {
+            println!(
+                "The quarantine of {} expired on {}, ask {} to fix it.",
+                entry.name, entry.expires, entry.owner
+            );
+        }
+
+        let mut quarantined = 0;
+
+        for case in &mut suite.cases {
+            let TestOutcome::Failed { errors } = &case.outcome else {
+                continue;
+            };
+
+            let Some(entry) = self.find(&case.full_name()) else {
+                continue;
+            };
+
+            let mut reason = format!(
+                "quarantined, owned by {} until {}",
+                entry.owner, entry.expires
+            );
+
+            if let Some(why) = &entry.reason {
+                reason = format!("{reason} ({why})");
+            }
+
+            if let Some(error) = errors.first() {
+                reason = format!("{reason} : {error}");
+            }
+
+            case.output.extend(errors.iter().cloned());
+            case.outcome = TestOutcome::Skipped { reason };
+            quarantined += 1;
+        }
+
+        quarantined
+    }
+}
+
+impl QuarantinedTest {
+    /* An expiry without a date, a bare time, never expires. */
+    pub fn is_expired(&self) -> bool {
+        match self.expires.date {
+            Some(date) => (date.year, date.month, date.day) <= today(),
+            None => false,
+        }
+    }
+}
+
+/* The current UTC date, from the days since the unix epoch. */
+fn today() -> (u16, u8, u8) {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+
+    // Howard Hinnant's civil_from_days.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year as u16, month as u8, day as u8)
+}
@@ -0,0 +1,128 @@
+use std::{collections::HashMap, fmt::Display, path::Path, str::FromStr};
+
+use serde::Serialize;
+
+use crate::utility::{hash, junit};
+
+/// One shard out of `count`, `index` is 1-based. Ex : `2/4`
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct ShardSpec {
+    pub index: usize,
+    pub count: usize,
+}
+
+/* Reads the duration of every test case in the previous JUnit reports, skipping the missing ones. */
+pub fn read_timings(paths: &[String]) -> HashMap<String, f64> {
+    let mut timings = HashMap::new();
+
+    for path in paths {
+        let cases = match junit::read_cases(Path::new(path)) {
+            Ok(cases) => cases,
+            Err(err) => {
+                println!("Skipping the timings of {path} : {err}");
+                continue;
+            }
+        };
+
+        for case in cases {
+            timings.insert(case.full_name(), case.duration);
+        }
+    }
+
+    timings
+}
+
+/* Picks the tests of the shard. Every agent has to get the same tests and timings for the shards to line up. */
+pub fn select(tests: &[String], shard: ShardSpec, timings: &HashMap<String, f64>) -> Vec<String> {
+    let mut tests = tests.to_vec();
+    tests.sort();
+    tests.dedup();
+
+    let known: Vec<f64> = tests
+        .iter()
+        .filter_map(|test| timings.get(test).copied())
+        .collect();
+
+    let mut selected: Vec<String> = match known.is_empty() {
+        true => tests
+            .into_iter()
+            .filter(|test| bucket(test, shard.count) == shard.index - 1)
+            .collect(),
+        false => {
+            // Tests without a previous run are assumed to take the average time.
+            let average = known.iter().sum::<f64>() / known.len() as f64;
+            balance(tests, shard, |test| {
+                timings.get(test).copied().unwrap_or(average)
+            })
+        }
+    };
+
+    selected.sort();
+    selected
+}
+
+/* FNV-1a barely mixes names that differ in their last characters, the multiply spreads them over the high bits. */
+fn bucket(test: &str, count: usize) -> usize {
+    let mixed = hash::fnv1a(&[test]).wrapping_mul(0x9e3779b97f4a7c15) >> 32;
+    (mixed % count as u64) as usize
+}
+
+/* Longest duration first into the least loaded shard, ties going to the name and the lowest shard. */
+fn balance(tests: Vec<String>, shard: ShardSpec, duration: impl Fn(&str) -> f64) -> Vec<String> {
+    let mut tests: Vec<(f64, String)> = tests
+        .into_iter()
+        .map(|test| (duration(&test), test))
+        .collect();
+
+    tests.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut loads = vec![0.0f64; shard.count];
+    let mut selected = Vec::new();
+
+    for (duration, test) in tests {
+        let lightest = loads
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1).then_with(|| a.0.cmp(&b.0)))
+            .map(|(index, _)| index)
+            .unwrap_or(0);
+
+        loads[lightest] += duration;
+
+        if lightest == shard.index - 1 {
+            selected.push(test);
+        }
+    }
+
+    selected
+}
+
+impl FromStr for ShardSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected INDEX/COUNT, Ex : 2/4 : {s}");
+
+        let (index, count) = s.trim().split_once('/').ok_or_else(invalid)?;
+        let index: usize = index.trim().parse().map_err(|_| invalid())?;
+        let count: usize = count.trim().parse().map_err(|_| invalid())?;
+
+        if count == 0 {
+            return Err(format!("the shard count can't be zero : {s}"));
+        }
+
+        if index == 0 || index > count {
+            return Err(format!(
+                "the shard index must be between 1 and {count} : {s}"
+            ));
+        }
+
+        Ok(ShardSpec { index, count })
+    }
+}
+
+impl Display for ShardSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
@@ -1,63 +1,228 @@
-use std::path::{Path, PathBuf};
+use std::{collections::HashSet, fmt::Display, path::Path};
 
-use ignore::{Walk, WalkBuilder};
+use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
 
-use crate::{unreal_engine::error::UnrealError, utility::search::SearchOptions};
-
-#[derive(Serialize, Deserialize, Debug)]
+use crate::{
+    unreal_engine::{
+        engine_association::EngineAssociation,
+        engine_index::EngineIndex,
+        engine_registry::{self, RegistryPaths},
+        error::UnrealError,
+        version_requirement::VersionRequirement,
+    },
+    utility::search::SearchOptions,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UnrealInstallation {
     pub exe_path: String,
     pub base_path: String,
     pub version: UnrealVersion,
+    pub kind: InstallationKind,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
+pub enum InstallationKind {
+    /// a prebuilt engine, from the launcher or an installed build.
+    Installed,
+    /// an engine built from source.
+    Source,
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+/// The contents of `Engine/Build/Build.version`.
+#[derive(Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct UnrealVersion {
     pub major_version: u16,
     pub minor_version: u16,
     pub patch_version: u16,
+    #[serde(default)]
+    pub changelist: u32,
+    #[serde(default)]
+    pub compatible_changelist: u32,
+    #[serde(default)]
+    pub is_licensee_version: u32,
+    #[serde(default)]
+    pub is_promoted_build: u32,
+    #[serde(default)]
+    pub branch_name: String,
+    #[serde(default, rename = "BuildId", skip_serializing_if = "Option::is_none")]
+    pub build_id: Option<String>,
 }
 
-impl TryFrom<SearchOptions> for UnrealInstallation {
-    type Error = UnrealError;
-
-    fn try_from(value: SearchOptions) -> Result<Self, Self::Error> {
-        Self::get_installation(value).ok_or(UnrealError::EngineNotFound)
+impl Display for UnrealVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self.major_version, self.minor_version, self.patch_version
+        )
     }
 }
 
 impl UnrealInstallation {
-    fn get_installation(search_options: SearchOptions) -> Option<UnrealInstallation> {
+    /* Resolves the engine a project's EngineAssociation points at, unless a version was requested on the command line. */
+    pub fn from_association(
+        association: &EngineAssociation,
+        uproject_path: &Path,
+        search_options: &SearchOptions,
+    ) -> Result<UnrealInstallation, UnrealError> {
+        if let Some(requirement) = search_options.version_override() {
+            return Self::find_matching(&requirement, search_options);
+        }
+
+        match association {
+            EngineAssociation::Version { major, minor } => {
+                let requirement = VersionRequirement::exact(*major, Some(*minor), None);
+                Self::find_matching(&requirement, search_options)
+            }
+            EngineAssociation::Guid(key) | EngineAssociation::Custom(key) => {
+                let entry = engine_registry::find_source_build(&RegistryPaths::from_env(), key)
+                    .ok_or_else(|| UnrealError::UnregisteredEngine {
+                        association: key.clone(),
+                    })?;
+
+                Self::from_engine_dir(&entry.root.join("Engine")).ok_or(UnrealError::EngineNotFound)
+            }
+            EngineAssociation::InTree => {
+                Self::find_in_tree(uproject_path).ok_or_else(|| UnrealError::InTreeEngineNotFound {
+                    project: uproject_path.to_string_lossy().to_string(),
+                })
+            }
+        }
+    }
+
+    /* Walks up from the project looking for the engine tree it lives in. */
+    fn find_in_tree(uproject_path: &Path) -> Option<UnrealInstallation> {
+        uproject_path
+            .ancestors()
+            .skip(1)
+            .find_map(|dir| Self::from_engine_dir(&dir.join("Engine")))
+    }
+
+    /* Finds an engine matching the requirement, preferring the newest registered engine. */
+    pub fn find_matching(
+        requirement: &VersionRequirement,
+        search_options: &SearchOptions,
+    ) -> Result<UnrealInstallation, UnrealError> {
+        if let Some(installation) = Self::from_registry(requirement) {
+            return Ok(installation);
+        }
+
+        let (installations, walked) =
+            Self::indexed_installations(search_options, search_options.rescan);
+        let mut found = Self::newest_matching(installations, requirement);
+
+        // An engine installed since the index was written is only found by walking again.
+        if found.is_none() && !walked {
+            let (installations, _) = Self::indexed_installations(search_options, true);
+            found = Self::newest_matching(installations, requirement);
+        }
+
+        found.ok_or_else(|| UnrealError::EngineVersionNotFound {
+            requirement: requirement.to_string(),
+        })
+    }
+
+    /* Lists every installation that is registered or found under the search roots, without duplicates. */
+    pub fn discover_all(
+        search_options: &SearchOptions,
+        registry_only: bool,
+    ) -> Vec<UnrealInstallation> {
+        let mut installations = Self::registered_installations();
+
+        if !registry_only {
+            let (indexed, _) = Self::indexed_installations(search_options, search_options.rescan);
+            installations.extend(indexed);
+        }
+
+        let mut seen = HashSet::new();
+
+        installations.retain(|installation| {
+            let base_path = Path::new(&installation.base_path);
+            let key = base_path
+                .canonicalize()
+                .unwrap_or_else(|_| base_path.to_path_buf());
+
+            seen.insert(key)
+        });
+
+        installations
+    }
+
+    /* The engines under the search roots, from the machine's engine index. Only the roots it doesn't cover are walked,
+    all of them on a rescan. Also returns whether a walk happened. */
+    fn indexed_installations(
+        search_options: &SearchOptions,
+        rescan: bool,
+    ) -> (Vec<UnrealInstallation>, bool) {
+        let mut index = match rescan {
+            true => EngineIndex::default(),
+            false => EngineIndex::load(),
+        };
+
+        let mut changed = index.revalidate();
+        let roots = index.uncovered_roots(search_options);
+
+        if !roots.is_empty() {
+            let found = Self::walk_search_roots(&roots, search_options.max_depth);
+            index.record(&roots, search_options.max_depth, &found);
+            changed = true;
+        }
+
+        // The walk's results are still used when the index can't be written.
+        if changed && let Err(err) = index.save() {
+            println!("Failed to save the engine index : {err}");
+        }
+
+        (index.installations(), !roots.is_empty())
+    }
+
+    fn newest_matching(
+        installations: Vec<UnrealInstallation>,
+        requirement: &VersionRequirement,
+    ) -> Option<UnrealInstallation> {
+        installations
+            .into_iter()
+            .filter(|installation| requirement.matches(&installation.version))
+            .max_by(|a, b| a.version.cmp(&b.version))
+    }
+
+    fn registered_installations() -> Vec<UnrealInstallation> {
+        engine_registry::registered_engines(&RegistryPaths::from_env())
+            .into_iter()
+            .filter_map(|entry| Self::from_engine_dir(&entry.root.join("Engine")))
+            .collect()
+    }
+
+    /* Checks the engines registered by the launcher and source builds, avoiding a filesystem walk. */
+    fn from_registry(requirement: &VersionRequirement) -> Option<UnrealInstallation> {
+        Self::newest_matching(Self::registered_installations(), requirement)
+    }
+
+    /* Walks the search roots for `Engine` directories, collecting every engine. */
+    fn walk_search_roots(roots: &[String], max_depth: usize) -> Vec<UnrealInstallation> {
         let (tx, rx) = std::sync::mpsc::channel::<UnrealInstallation>();
         let num_cpus = num_cpus::get();
 
-        let found = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
-
-        for root in search_options.search_dir {
+        for root in roots {
             if !Path::new(&root).exists() {
                 continue;
             }
 
             let tx = tx.clone();
-            let cl = found.clone();
 
-            let walker = WalkBuilder::new(&root)
-                .max_depth(Some(search_options.max_depth))
+            let walker = WalkBuilder::new(root)
+                .max_depth(Some(max_depth))
                 .threads(num_cpus)
                 .follow_links(false)
                 .build_parallel();
 
             walker.run(move || {
                 let tx = tx.clone();
-                let found = cl.clone();
 
                 Box::new(move |result: Result<ignore::DirEntry, ignore::Error>| {
-                    if found.load(std::sync::atomic::Ordering::Relaxed) {
-                        return ignore::WalkState::Quit;
-                    }
-
                     let entry = match result {
                         Ok(entry) => entry,
                         Err(_) => return ignore::WalkState::Continue,
@@ -78,37 +243,21 @@ impl UnrealInstallation {
                         return ignore::WalkState::Continue;
                     }
 
-                    let installation = Self::from_path(
-                        path,
-                        search_options.ue_major_version,
-                        search_options.ue_minor_version,
-                    );
-
-                    if let Some(installation) = installation {
-                        found.store(true, std::sync::atomic::Ordering::Relaxed);
+                    if let Some(installation) = Self::from_engine_dir(path) {
                         let _ = tx.send(installation);
-                        return ignore::WalkState::Quit;
                     }
 
                     ignore::WalkState::Skip
                 })
             });
-
-            if found.load(std::sync::atomic::Ordering::Relaxed) {
-                break;
-            }
         }
 
         drop(tx);
-        rx.recv().ok()
+        rx.into_iter().collect()
     }
 
-    // Helper function to validate and create UnrealInstallation
-    fn from_path(
-        engine_path: &std::path::Path,
-        target_major: u16,
-        target_minor: u16,
-    ) -> Option<UnrealInstallation> {
+    /* Reads the installation rooted at the given `Engine` directory, regardless of its version. */
+    pub fn from_engine_dir(engine_path: &Path) -> Option<UnrealInstallation> {
         let base_path = engine_path.parent()?.to_path_buf();
         let build_version_path = engine_path.join("Build").join("Build.version");
 
@@ -121,15 +270,22 @@ impl UnrealInstallation {
         let file = std::fs::File::open(&build_version_path).ok()?;
         let version: UnrealVersion = serde_json::from_reader(file).ok()?;
 
-        if version.major_version == target_major && version.minor_version == target_minor {
-            Some(UnrealInstallation {
-                base_path: base_path.to_string_lossy().to_string(),
-                exe_path: exe_path.to_string_lossy().to_string(),
-                version,
-            })
-        } else {
-            None
-        }
+        // Installed builds, including the launcher's, ship this marker. Source builds never have it.
+        let kind = match engine_path
+            .join("Build")
+            .join("InstalledBuild.txt")
+            .exists()
+        {
+            true => InstallationKind::Installed,
+            false => InstallationKind::Source,
+        };
+
+        Some(UnrealInstallation {
+            base_path: base_path.to_string_lossy().to_string(),
+            exe_path: exe_path.to_string_lossy().to_string(),
+            version,
+            kind,
+        })
     }
 
     fn find_executable(engine_path: &std::path::Path) -> Option<std::path::PathBuf> {
@@ -143,24 +299,4 @@ impl UnrealInstallation {
 
         candidates.into_iter().find(|path| path.exists())
     }
-
-    pub fn find_file(&self, target_name: impl AsRef<str>) -> Option<PathBuf> {
-        Walk::new(&self.base_path).into_iter().find_map(|entry| {
-            let entry = entry.ok()?;
-
-            let path = entry.path();
-
-            if !path.is_file() {
-                return None;
-            }
-
-            let name = path.file_name()?;
-
-            if name.eq(target_name.as_ref()) {
-                return Some(path.to_path_buf());
-            }
-
-            None
-        })
-    }
 }
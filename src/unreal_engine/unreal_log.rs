@@ -0,0 +1,384 @@
+use std::{collections::BTreeMap, fmt::Display, path::Path};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::unreal_engine::{args::LogsArgs, error::UnrealError};
+
+/// The verbosity of a log line, ordered from the most to the least severe.
+#[derive(ValueEnum, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+#[value(rename_all = "PascalCase")]
+pub enum Verbosity {
+    Fatal,
+    Error,
+    Warning,
+    Display,
+    Log,
+    Verbose,
+    #[value(alias = "very-verbose")]
+    VeryVerbose,
+}
+
+#[derive(ValueEnum, Serialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+/// One `[2024.01.01-12.00.00:000][  0]LogCategory: Verbosity: message` line.
+/// Lines without a category, like UnrealBuildTool's, only have a message.
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct LogRecord {
+    pub timestamp: Option<String>,
+    pub frame: Option<u64>,
+    pub category: Option<String>,
+    /// `Log` when the line doesn't name one.
+    pub verbosity: Verbosity,
+    pub message: String,
+}
+
+/// The categories to keep, and the least severe verbosity to keep.
+#[derive(Default)]
+pub struct LogFilter {
+    pub categories: Vec<String>,
+    pub verbosity: Option<Verbosity>,
+}
+
+/// How many messages each category logged, per verbosity.
+pub type CategoryCounts = BTreeMap<String, BTreeMap<Verbosity, usize>>;
+
+#[derive(Serialize)]
+struct LogReport<'a> {
+    records: &'a [LogRecord],
+    counts: &'a CategoryCounts,
+}
+
+impl Verbosity {
+    fn from_name(name: &str) -> Option<Verbosity> {
+        match name {
+            "Fatal" => Some(Verbosity::Fatal),
+            "Error" => Some(Verbosity::Error),
+            "Warning" => Some(Verbosity::Warning),
+            "Display" => Some(Verbosity::Display),
+            "Log" => Some(Verbosity::Log),
+            "Verbose" => Some(Verbosity::Verbose),
+            "VeryVerbose" => Some(Verbosity::VeryVerbose),
+            _ => None,
+        }
+    }
+}
+
+impl LogRecord {
+    pub fn parse(line: &str) -> LogRecord {
+        let mut rest = line.trim_end();
+
+        let timestamp = take_bracket(&mut rest).map(str::to_owned);
+        let frame = timestamp
+            .as_ref()
+            .and_then(|_| take_bracket(&mut rest))
+            .and_then(|frame| frame.trim().parse().ok());
+
+        let (category, rest) = match rest.split_once(": ") {
+            Some((category, message)) if is_category(category) => {
+                (Some(category.to_owned()), message)
+            }
+            _ => (None, rest),
+        };
+
+        // The message keeps its leading whitespace, `Automation List` indents the test names with a tab.
+        let (verbosity, message) = match (&category, rest.split_once(": ")) {
+            (Some(_), Some((name, message))) => match Verbosity::from_name(name) {
+                Some(verbosity) => (verbosity, message),
+                None => (Verbosity::Log, rest),
+            },
+            _ => (Verbosity::Log, rest),
+        };
+
+        LogRecord {
+            timestamp,
+            frame,
+            category,
+            verbosity,
+            message: message.to_owned(),
+        }
+    }
+
+    pub fn is_category(&self, category: &str) -> bool {
+        self.category
+            .as_deref()
+            .is_some_and(|own| own.eq_ignore_ascii_case(category))
+    }
+
+    /* The line without its timestamp and frame, Ex : `LogPython: Error: message` */
+    pub fn categorized(&self) -> String {
+        let mut line = String::new();
+
+        if let Some(category) = &self.category {
+            line.push_str(category);
+            line.push_str(": ");
+
+            if self.verbosity != Verbosity::Log {
+                line.push_str(&format!("{:?}: ", self.verbosity));
+            }
+        }
+
+        line.push_str(&self.message);
+        line
+    }
+}
+
+impl LogFilter {
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let category = self.categories.is_empty()
+            || self
+                .categories
+                .iter()
+                .any(|category| record.is_category(category));
+
+        let verbosity = self
+            .verbosity
+            .is_none_or(|verbosity| record.verbosity <= verbosity);
+
+        category && verbosity
+    }
+}
+
+/* Counts the records per category and verbosity, lines without a category are counted as `Uncategorized`. */
+pub fn count(records: &[LogRecord]) -> CategoryCounts {
+    let mut counts = CategoryCounts::new();
+
+    for record in records {
+        let category = record.category.as_deref().unwrap_or("Uncategorized");
+
+        *counts
+            .entry(category.to_owned())
+            .or_default()
+            .entry(record.verbosity)
+            .or_default() += 1;
+    }
+
+    counts
+}
+
+/* Reads a log file, the editor writes it with a UTF-8 BOM. */
+pub fn read_file(path: &Path) -> Result<Vec<LogRecord>, UnrealError> {
+    let contents = std::fs::read(path)?;
+    let contents = contents.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&contents);
+
+    Ok(String::from_utf8_lossy(contents)
+        .lines()
+        .map(LogRecord::parse)
+        .collect())
+}
+
+/* Filters a log file, printing its lines or the message count of each category. */
+pub fn process_logs_command(args: &LogsArgs) -> Result<(), UnrealError> {
+    let filter = LogFilter {
+        categories: args.categories.clone(),
+        verbosity: args.verbosity,
+    };
+
+    let records: Vec<LogRecord> = read_file(Path::new(&args.file))?
+        .into_iter()
+        .filter(|record| filter.matches(record))
+        .collect();
+
+    let counts = count(&records);
+
+    match (args.format, args.counts) {
+        (LogFormat::Json, true) => println!("{}", serde_json::to_string_pretty(&counts)?),
+        (LogFormat::Json, false) => {
+            let report = LogReport {
+                records: &records,
+                counts: &counts,
+            };
+
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        (LogFormat::Text, true) => print_counts(&counts),
+        (LogFormat::Text, false) => records.iter().for_each(|record| println!("{record}")),
+    }
+
+    Ok(())
+}
+
+fn print_counts(counts: &CategoryCounts) {
+    println!(
+        "{:<40} {:>8} {:>8} {:>8}",
+        "Category", "Errors", "Warnings", "Total"
+    );
+
+    for (category, verbosities) in counts {
+        let count = |verbosity: Verbosity| verbosities.get(&verbosity).copied().unwrap_or(0);
+
+        println!(
+            "{:<40} {:>8} {:>8} {:>8}",
+            category,
+            count(Verbosity::Fatal) + count(Verbosity::Error),
+            count(Verbosity::Warning),
+            verbosities.values().sum::<usize>()
+        );
+    }
+}
+
+/* Takes a leading `[...]` group off the line. */
+fn take_bracket<'a>(rest: &mut &'a str) -> Option<&'a str> {
+    let (inner, after) = rest.strip_prefix('[')?.split_once(']')?;
+    *rest = after;
+    Some(inner)
+}
+
+/* Categories are identifiers, which tells them apart from `file.cpp(12): error` and prose. */
+fn is_category(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Display for LogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(timestamp) = &self.timestamp {
+            write!(f, "[{timestamp}]")?;
+        }
+
+        if let Some(frame) = self.frame {
+            write!(f, "[{frame:>3}]")?;
+        }
+
+        write!(f, "{}", self.categorized())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        prefix: Option<(&str, u64)>,
+        category: Option<&str>,
+        verbosity: Verbosity,
+        message: &str,
+    ) -> LogRecord {
+        LogRecord {
+            timestamp: prefix.map(|(timestamp, _)| timestamp.to_owned()),
+            frame: prefix.map(|(_, frame)| frame),
+            category: category.map(str::to_owned),
+            verbosity,
+            message: message.to_owned(),
+        }
+    }
+
+    #[test]
+    fn parses_log_lines() {
+        let cases = [
+            (
+                "[2024.01.01-12.00.00:000][  0]LogInit: Display: Engine is initialized.",
+                record(
+                    Some(("2024.01.01-12.00.00:000", 0)),
+                    Some("LogInit"),
+                    Verbosity::Display,
+                    "Engine is initialized.",
+                ),
+            ),
+            (
+                "[2024.01.01-12.00.01:250][ 42]LogPython: Error: Traceback: line 3\r",
+                record(
+                    Some(("2024.01.01-12.00.01:250", 42)),
+                    Some("LogPython"),
+                    Verbosity::Error,
+                    "Traceback: line 3",
+                ),
+            ),
+            (
+                "LogAutomationCommandLine: \tProject.Demo.A",
+                record(
+                    None,
+                    Some("LogAutomationCommandLine"),
+                    Verbosity::Log,
+                    "\tProject.Demo.A",
+                ),
+            ),
+            (
+                "LogTemp: NotAVerbosity: kept in the message",
+                record(
+                    None,
+                    Some("LogTemp"),
+                    Verbosity::Log,
+                    "NotAVerbosity: kept in the message",
+                ),
+            ),
+            (
+                r"C:\Game\Actor.cpp(12): error C2065: 'x': undeclared identifier",
+                record(
+                    None,
+                    None,
+                    Verbosity::Log,
+                    r"C:\Game\Actor.cpp(12): error C2065: 'x': undeclared identifier",
+                ),
+            ),
+            (
+                "Total execution time: 1.23 seconds",
+                record(
+                    None,
+                    None,
+                    Verbosity::Log,
+                    "Total execution time: 1.23 seconds",
+                ),
+            ),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(LogRecord::parse(line), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn displays_the_parsed_line() {
+        for line in [
+            "[2024.01.01-12.00.00:000][  0]LogInit: Display: Engine is initialized.",
+            "[2024.01.01-12.00.00:000][123]LogTemp: message",
+            "Using 'git status' to determine working set",
+        ] {
+            assert_eq!(LogRecord::parse(line).to_string(), line);
+        }
+    }
+
+    #[test]
+    fn filters_by_category_and_verbosity() {
+        let filter = LogFilter {
+            categories: vec!["logpython".to_owned()],
+            verbosity: Some(Verbosity::Warning),
+        };
+
+        let cases = [
+            ("LogPython: Error: failed", true),
+            ("LogPython: Warning: deprecated", true),
+            ("LogPython: Display: loaded", false),
+            ("LogInit: Error: failed", false),
+            ("ERROR: no category", false),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(filter.matches(&LogRecord::parse(line)), expected, "{line}");
+        }
+        assert!(LogFilter::default().matches(&LogRecord::parse("anything")));
+    }
+
+    #[test]
+    fn counts_per_category_and_verbosity() {
+        let records: Vec<LogRecord> = [
+            "LogPython: Error: failed",
+            "LogPython: Error: failed again",
+            "LogPython: Warning: deprecated",
+            "no category",
+        ]
+        .into_iter()
+        .map(LogRecord::parse)
+        .collect();
+
+        let counts = count(&records);
+
+        assert_eq!(counts["LogPython"][&Verbosity::Error], 2);
+        assert_eq!(counts["LogPython"][&Verbosity::Warning], 1);
+        assert_eq!(counts["Uncategorized"][&Verbosity::Log], 1);
+    }
+}
@@ -1,218 +1,320 @@
 use std::{
-    borrow::Borrow,
-    io::{self, BufRead, BufReader, ErrorKind, Write},
-    path::Path,
-    process::{Child, Command, Stdio},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
 };
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    unreal_engine::{error::UnrealError, unreal_installation::UnrealInstallation},
+    unreal_engine::{
+        annotations,
+        args::{BuildArgs, RunArgs},
+        build_matrix::{self, BuildCell},
+        build_target::{BuildTarget, TargetSelector},
+        diagnostics::{self, DiagnosticCollector},
+        engine_association::EngineAssociation,
+        error::UnrealError,
+        host_platform, log_follow, process,
+        project_cache::{self, CacheMiss},
+        project_descriptor::ProjectDescriptor,
+        unreal_installation::UnrealInstallation,
+        unreal_log::LogFilter,
+        warning_baseline,
+        workspace::{EngineLookup, SKIPPED_DIRS},
+    },
     utility::{
         path_utility::{self},
+        repository,
         search::{self, SearchOptions},
     },
 };
 
 #[derive(Serialize, Deserialize)]
 pub struct UnrealProject {
-    unreal_version: f32,
+    engine_association: EngineAssociation,
     name: String,
     associated_engine: UnrealInstallation,
     path: String,
 }
 
-#[derive(Deserialize)]
-struct UprojectFile {
-    #[serde(alias = "EngineAssociation")]
-    unreal_version: String,
-}
+impl TryFrom<SearchOptions> for UnrealProject {
+    type Error = UnrealError;
 
-impl TryFrom<&Path> for UprojectFile {
-    type Error = std::io::Error;
+    fn try_from(value: SearchOptions) -> Result<Self, Self::Error> {
+        let uproject_path = Self::find_uproject(&value)?;
+        Self::load(&uproject_path, &value, &mut EngineLookup::default())
+    }
+}
 
-    fn try_from(value: &Path) -> Result<Self, Self::Error> {
-        let file = std::fs::read(value)?;
+impl UnrealProject {
+    /* Finds the .uproject the way git finds .git, walking up from the project directory to the repository root.
+    When there is none on the way, the projects below the directory are considered. --project picks one by name,
+    from anywhere below the repository root. */
+    pub fn find_uproject(options: &SearchOptions) -> Result<PathBuf, UnrealError> {
+        let start = path_utility::absolute_dir(&options.project_directory)
+            .ok_or(UnrealError::ProjectNotFound)?;
+
+        let root = repository::repository_root(&start);
+        let name = options.project_name.as_deref();
+
+        for dir in start.ancestors() {
+            let candidates =
+                Self::named_projects(search::files_with_extension(dir, "uproject"), name);
+
+            if !candidates.is_empty() {
+                return Self::single_project(candidates);
+            }
 
-        let result = serde_json::from_slice::<Self>(&file);
+            // The root is canonical, the directories on the way keep the spelling they were given with.
+            let canonical = dir.canonicalize().ok().map(path_utility::strip_verbatim);
 
-        match result {
-            Ok(uproject) => Ok(uproject),
-            Err(err) => Err(io::Error::other(err)),
+            if canonical.is_some() && canonical == root {
+                break;
+            }
         }
-    }
-}
-
-impl TryFrom<SearchOptions> for UnrealProject {
-    type Error = UnrealError;
 
-    fn try_from(value: SearchOptions) -> Result<Self, Self::Error> {
-        let cwd = &value.project_directory;
+        // A named project may live anywhere in the repository, like the ones --projects picks.
+        let search_root = match name {
+            Some(_) => root.as_deref().unwrap_or(&start),
+            None => &start,
+        };
 
-        if let Ok(project) = Self::load_cached_project(cwd) {
-            return Ok(project);
+        let candidates = Self::named_projects(
+            search::files_below(search_root, "uproject", options.max_depth, SKIPPED_DIRS),
+            name,
+        );
+
+        match (candidates.is_empty(), name) {
+            (true, Some(name)) => Err(UnrealError::NamedProjectNotFound {
+                name: name.to_owned(),
+            }),
+            (true, None) => Err(UnrealError::ProjectNotFound),
+            (false, _) => Self::single_project(candidates),
         }
+    }
 
-        let uproject_path =
-            search::file_with_extension(cwd, "uproject").ok_or(UnrealError::ProjectNotFound)?;
+    /* Loads the project from its cache, or resolves its engine through the lookup and caches it. */
+    pub fn load(
+        uproject_path: &Path,
+        options: &SearchOptions,
+        engines: &mut EngineLookup,
+    ) -> Result<UnrealProject, UnrealError> {
+        let engine_override = options
+            .version_override()
+            .map(|requirement| requirement.to_string());
+
+        // A rescan resolves the engine again, the cache would skip it.
+        if !options.rescan {
+            match project_cache::load(uproject_path, engine_override.as_deref()) {
+                Ok(project) => return Ok(project),
+                Err(CacheMiss::Missing) => {}
+                Err(miss) => println!("Resolving the project again, {miss}."),
+            }
+        }
 
-        let uproject = UprojectFile::try_from(uproject_path.as_path())?;
+        let descriptor = ProjectDescriptor::try_from(uproject_path)?;
+        let engine_association = descriptor.engine_association();
 
-        let name = Self::extract_project_name(&uproject_path)?;
-        let version = Self::parse_version(&uproject.unreal_version)?;
+        let name = Self::extract_project_name(uproject_path)?;
 
-        let unreal_installation = UnrealInstallation::try_from(value)?;
-        println!("{unreal_installation:?}");
+        let unreal_installation = engines.resolve(&engine_association, uproject_path, options)?;
 
         let project = UnrealProject {
-            unreal_version: version,
+            engine_association,
             name,
             associated_engine: unreal_installation,
             path: uproject_path.to_string_lossy().to_string(),
         };
 
-        let _ = Self::save_project(&project);
+        // The project is still usable when the cache can't be written, like in a read-only checkout.
+        match project_cache::save(&project, engine_override.as_deref()) {
+            Ok(path) => println!("Cached Project : {}", path.display()),
+            Err(err) => println!("Failed to cache the project : {err}"),
+        }
+
         Ok(project)
     }
-}
 
-impl UnrealProject {
-    fn extract_project_name(uproject_path: &Path) -> Result<String, UnrealError> {
-        let name = path_utility::remove_extension(uproject_path);
-        path_utility::filename_as_string(&name).ok_or(UnrealError::ProjectNotFound)
-    }
+    fn named_projects(candidates: Vec<PathBuf>, name: Option<&str>) -> Vec<PathBuf> {
+        let Some(name) = name else {
+            return candidates;
+        };
 
-    fn parse_version(version_str: &str) -> Result<f32, UnrealError> {
-        version_str.parse().or(Err(UnrealError::ProjectNotFound))
+        candidates
+            .into_iter()
+            .filter(|path| {
+                path.file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(name))
+            })
+            .collect()
     }
 
-    /* Attempts to save the UnrealProject to a unreal.project file. */
-    fn save_project(project: &UnrealProject) -> io::Result<()> {
-        let mut file = std::fs::File::create("unreal.project")?;
+    fn single_project(mut candidates: Vec<PathBuf>) -> Result<PathBuf, UnrealError> {
+        if candidates.len() == 1 {
+            return Ok(candidates.remove(0));
+        }
 
-        let toml_str = match toml::to_string_pretty(project) {
-            Ok(str) => Ok(str),
-            Err(err) => Err(std::io::Error::other(err.to_string())),
-        };
+        let candidates = candidates
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
 
-        let result = toml_str.and_then(|str| write!(&mut file, "{}", &str));
+        Err(UnrealError::AmbiguousProject { candidates })
+    }
 
-        if result.is_ok() {
-            println!("Saved Project.");
-        }
+    fn extract_project_name(uproject_path: &Path) -> Result<String, UnrealError> {
+        let name = path_utility::remove_extension(uproject_path);
+        path_utility::filename_as_string(&name).ok_or(UnrealError::ProjectNotFound)
+    }
 
-        result.map_err(|err| io::Error::other(err.to_string()))
+    pub fn path(&self) -> &Path {
+        Path::new(&self.path)
     }
 
-    /* Will attempt to load an UnrealProject from a .project file in the current directory, returns None if it fails. */
-    fn load_cached_project(cwd: impl AsRef<Path>) -> io::Result<UnrealProject> {
-        let path = search::file_with_extension(cwd, "project")
-            .ok_or(io::Error::from(ErrorKind::NotFound))?;
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-        let handle = std::fs::File::open(&path)?;
-        let project = serde_json::from_reader(&handle)?;
+    pub fn engine(&self) -> &UnrealInstallation {
+        &self.associated_engine
+    }
 
-        Ok(project)
+    pub fn project_dir(&self) -> &Path {
+        Path::new(&self.path).parent().unwrap_or(Path::new("."))
     }
 
-    pub fn build_project(&self) -> Result<(), UnrealError> {
-        let file = self
-            .associated_engine
-            .find_file("Build.bat")
-            .ok_or(UnrealError::EngineNotFound)?;
-
-        let build_bat = file.to_str().unwrap();
-
-        let args = &[
-            "/C",
-            "call",
-            build_bat,
-            &format!("{}Editor", self.name),
-            #[cfg(target_os = "windows")]
-            "Win64",
-            #[cfg(not(target_os = "windows"))]
-            "Linux",
-            "Development",
-            &self.path,
-            "-waitmutex",
-            "-NoHotReload",
-        ];
+    /* Whether the .uproject enables the plugin, engine plugins enabled by default aren't listed and count as disabled. */
+    pub fn is_plugin_enabled(&self, plugin: &str) -> Result<bool, UnrealError> {
+        let descriptor = ProjectDescriptor::try_from(self.path())?;
+        Ok(descriptor.is_plugin_enabled(plugin))
+    }
 
-        println!("\nStarting Project Compilation..");
-        let mut child = create_child_process(args);
+    /* Resolves the requested target against the project's `*.Target.cs` files, before UBT is started. */
+    pub fn resolve_target(&self, target: &TargetSelector) -> Result<BuildTarget, UnrealError> {
+        let targets = BuildTarget::discover(self.project_dir());
+        target.resolve(&self.name, &targets)
+    }
 
-        monitor_output(&mut child);
+    pub fn build_project(&self, args: &BuildArgs) -> Result<(), UnrealError> {
+        let cells = args.matrix_cells()?;
+        let collector = DiagnosticCollector::default();
 
-        let status = match child.wait() {
-            Ok(status) => status,
-            Err(err) => return Err(UnrealError::IoError { error: err }),
+        let result = match cells.is_empty() {
+            true => self.build_cell(&args.single_cell(), &args.platform, None, false, &collector),
+            false => build_matrix::run(self, args, &cells, &collector),
         };
 
-        if !status.success() {
-            return Err(UnrealError::FailedExitStatus { status });
+        let diagnostics = collector.into_diagnostics();
+        diagnostics::print_summary(&diagnostics);
+
+        if let Some(path) = &args.diagnostics_path {
+            diagnostics::write_json(&diagnostics, Path::new(path))?;
+        }
+
+        if let Some(format) = args.annotations {
+            annotations::emit(
+                &diagnostics,
+                format,
+                Path::new(&self.path),
+                Path::new(&args.annotations_path),
+            )?;
+        }
+
+        // A failed build has an incomplete set of warnings, so it's only compared once the build passed.
+        result?;
+
+        match &args.warning_baseline {
+            Some(path) => warning_baseline::check(
+                &diagnostics,
+                Path::new(path),
+                Path::new(&self.path),
+                args.update_baseline,
+            ),
+            None => Ok(()),
         }
+    }
 
-        Ok(())
+    /* Builds a single target, configuration and platform combination. Output lines are prefixed with the label, if any.
+    A concurrent cell skips UBT's mutex, waiting on it would serialize the cells and count the wait in their durations.
+    The caller keeps the cells sharing a target and platform apart, they share UHT's output and the target's makefile. */
+    pub fn build_cell(
+        &self,
+        cell: &BuildCell,
+        default_platform: &str,
+        label: Option<&str>,
+        concurrent: bool,
+        collector: &DiagnosticCollector,
+    ) -> Result<(), UnrealError> {
+        let target = self.resolve_target(&cell.target)?;
+        let script = host_platform::build_script(&self.associated_engine)?;
+
+        let mut command = host_platform::script_command(&script);
+        command.args([
+            target.name.as_str(),
+            cell.platform(default_platform),
+            cell.configuration.as_str(),
+            &self.path,
+            match concurrent {
+                true => "-NoMutex",
+                false => "-waitmutex",
+            },
+            "-NoHotReload",
+        ]);
+
+        println!(
+            "\nStarting Project Compilation : {} {} {}..",
+            target.name,
+            cell.configuration.as_str(),
+            cell.platform(default_platform)
+        );
+        process::run_to_completion(command, label, Some(&|line| collector.observe(line)))
     }
 
-    pub fn build_and_start(self) -> Result<(), UnrealError> {
-        self.build_project().and_then(|_| self.start_project())
+    pub fn build_and_start(self, args: &BuildArgs) -> Result<(), UnrealError> {
+        self.build_project(args).and_then(|_| self.start_project())
     }
 
     pub fn start_project(self) -> Result<(), UnrealError> {
-        let args = &["/C", "start", &self.associated_engine.exe_path, &self.path];
+        let mut command =
+            host_platform::editor_command(Path::new(&self.associated_engine.exe_path));
+        command.arg(&self.path);
 
         println!("\nStarting Project..");
-        let mut child = create_child_process(args);
-
-        monitor_output(&mut child);
-
-        let status = match child.wait() {
-            Ok(status) => status,
-            Err(err) => return Err(UnrealError::IoError { error: err }),
-        };
-
-        if !status.success() {
-            return Err(UnrealError::FailedExitStatus { status });
-        }
-
-        Ok(())
+        process::run_to_completion(command, None, None)
     }
-}
 
-#[inline]
-fn create_child_process(args: &[&str]) -> Child {
-    Command::new("cmd")
-        .args(args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .expect("Failed to start build command.")
-}
-
-fn monitor_output(child: &mut Child) {
-    let stdout = BufReader::new(child.stdout.take().expect("No stdout"));
-    let stderr = BufReader::new(child.stderr.take().expect("No stderr"));
-
-    let out_lines = stdout.lines();
-    let err_lines = stderr.lines();
-
-    let barrier = std::sync::Barrier::new(2);
+    /* Starts the editor and prints its log file until it exits. The editor is spawned directly,
+    `cmd /C start` would return before it does. */
+    pub fn follow_project(self, args: &RunArgs) -> Result<(), UnrealError> {
+        let log_path = self
+            .project_dir()
+            .join("Saved")
+            .join("Logs")
+            .join(format!("{}.log", self.name));
+
+        let mut command = Command::new(&self.associated_engine.exe_path);
+        command
+            .arg(&self.path)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+
+        let filter = LogFilter {
+            categories: args.categories.clone(),
+            verbosity: args.verbosity,
+        };
 
-    rayon::scope(|s| {
-        s.spawn(|_| {
-            for line in out_lines.into_iter().map_while(Result::ok) {
-                println!("{line}");
-            }
+        let color = !args.no_color && std::env::var_os("NO_COLOR").is_none();
 
-            barrier.wait();
-        });
+        println!(
+            "\nStarting Project, following {}..",
+            log_path.to_string_lossy()
+        );
+        let status = log_follow::follow(command, &log_path, &filter, color)?;
 
-        for line in err_lines.into_iter().map_while(Result::ok) {
-            eprintln!("{line}");
+        match status.success() {
+            true => Ok(()),
+            false => Err(UnrealError::FailedExitStatus { status }),
         }
-
-        barrier.wait();
-    });
+    }
 }
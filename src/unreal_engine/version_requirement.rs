@@ -0,0 +1,218 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use serde::Serialize;
+
+use crate::unreal_engine::unreal_installation::UnrealVersion;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Operator {
+    Equal,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+}
+
+/// A single comparison against a partial version, Ex : `>=5.4`.
+#[derive(PartialEq, Debug, Clone)]
+struct Comparator {
+    operator: Operator,
+    /// the specified components, only these are compared. `5.4` matches every 5.4 patch.
+    version: Vec<u16>,
+}
+
+/// A set of comparators that must all match, Ex : `>=5.4, <5.7`.
+#[derive(Serialize, PartialEq, Debug, Clone)]
+#[serde(into = "String")]
+pub struct VersionRequirement {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionRequirement {
+    /* A requirement matching the given components exactly, the missing components match anything. */
+    pub fn exact(major: u16, minor: Option<u16>, patch: Option<u16>) -> Self {
+        let version = [Some(major), minor, patch]
+            .into_iter()
+            .map_while(|component| component)
+            .collect();
+
+        VersionRequirement {
+            comparators: vec![Comparator {
+                operator: Operator::Equal,
+                version,
+            }],
+        }
+    }
+
+    pub fn matches(&self, version: &UnrealVersion) -> bool {
+        let components = [
+            version.major_version,
+            version.minor_version,
+            version.patch_version,
+        ];
+
+        self.comparators.iter().all(|comparator| {
+            let ordering = components[..comparator.version.len()].cmp(&comparator.version);
+
+            match comparator.operator {
+                Operator::Equal => ordering == Ordering::Equal,
+                Operator::Greater => ordering == Ordering::Greater,
+                Operator::GreaterEqual => ordering != Ordering::Less,
+                Operator::Less => ordering == Ordering::Less,
+                Operator::LessEqual => ordering != Ordering::Greater,
+            }
+        })
+    }
+}
+
+impl FromStr for Comparator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (operator, version) = [
+            (">=", Operator::GreaterEqual),
+            ("<=", Operator::LessEqual),
+            ("==", Operator::Equal),
+            (">", Operator::Greater),
+            ("<", Operator::Less),
+            ("=", Operator::Equal),
+        ]
+        .into_iter()
+        .find_map(|(prefix, operator)| Some((operator, s.strip_prefix(prefix)?)))
+        .unwrap_or((Operator::Equal, s));
+
+        let version = version
+            .trim()
+            .split('.')
+            .map(|component| component.parse::<u16>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("invalid version in requirement : {s}"))?;
+
+        if version.len() > 3 {
+            return Err(format!("expected at most major.minor.patch : {s}"));
+        }
+
+        Ok(Comparator { operator, version })
+    }
+}
+
+impl FromStr for VersionRequirement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let comparators = s
+            .split(',')
+            .map(Comparator::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(VersionRequirement { comparators })
+    }
+}
+
+impl Display for Comparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operator = match self.operator {
+            Operator::Equal => "=",
+            Operator::Greater => ">",
+            Operator::GreaterEqual => ">=",
+            Operator::Less => "<",
+            Operator::LessEqual => "<=",
+        };
+
+        let version = self
+            .version
+            .iter()
+            .map(u16::to_string)
+            .collect::<Vec<_>>()
+            .join(".");
+
+        write!(f, "{operator}{version}")
+    }
+}
+
+impl Display for VersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let comparators = self
+            .comparators
+            .iter()
+            .map(Comparator::to_string)
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", comparators.join(", "))
+    }
+}
+
+impl From<VersionRequirement> for String {
+    fn from(value: VersionRequirement) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(major_version: u16, minor_version: u16, patch_version: u16) -> UnrealVersion {
+        UnrealVersion {
+            major_version,
+            minor_version,
+            patch_version,
+            changelist: 0,
+            compatible_changelist: 0,
+            is_licensee_version: 0,
+            is_promoted_build: 0,
+            branch_name: String::new(),
+            build_id: None,
+        }
+    }
+
+    #[test]
+    fn parses_and_displays_requirements() {
+        let cases = [
+            ("5.4", "=5.4"),
+            ("==5.4.1", "=5.4.1"),
+            (">=5.4, <5.7", ">=5.4, <5.7"),
+            (" > 5 ,<= 5.6.2 ", ">5, <=5.6.2"),
+        ];
+
+        for (input, expected) in cases {
+            let requirement: VersionRequirement = input.parse().unwrap();
+
+            assert_eq!(requirement.to_string(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_requirements() {
+        for input in ["", "5.x", ">=5.4,", "~5.4", "5.4.1.2", "70000"] {
+            assert!(input.parse::<VersionRequirement>().is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn matches_only_the_specified_components() {
+        let cases = [
+            ("5.4", version(5, 4, 3), true),
+            ("5.4", version(5, 5, 0), false),
+            ("5", version(5, 0, 0), true),
+            ("5.4.3", version(5, 4, 2), false),
+            (">5.4", version(5, 4, 9), false),
+            (">5.4", version(5, 5, 0), true),
+            (">=5.4, <5.7", version(5, 6, 1), true),
+            (">=5.4, <5.7", version(5, 7, 0), false),
+            (">=5.4, <5.7", version(5, 3, 2), false),
+            ("<=5.6", version(5, 6, 9), true),
+            ("<5", version(4, 27, 2), true),
+        ];
+
+        for (input, version, expected) in cases {
+            let requirement: VersionRequirement = input.parse().unwrap();
+
+            assert_eq!(requirement.matches(&version), expected, "{input} {version}");
+        }
+        assert!(VersionRequirement::exact(5, Some(4), None).matches(&version(5, 4, 1)));
+        assert!(!VersionRequirement::exact(5, Some(4), Some(0)).matches(&version(5, 4, 1)));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    unreal_engine::{
+        diagnostics::{Diagnostic, Severity},
+        error::UnrealError,
+    },
+    utility::repository,
+};
+
+const BASELINE_VERSION: u32 = 1;
+
+/// The accepted warnings of a project, keyed without line numbers so unrelated edits don't invalidate it.
+#[derive(Serialize, Deserialize)]
+struct WarningBaseline {
+    version: u32,
+    warnings: Vec<BaselineEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineEntry {
+    file: String,
+    code: String,
+    message: String,
+    count: usize,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+struct WarningKey {
+    file: String,
+    code: String,
+    message: String,
+}
+
+/* Compares the build's warnings against the baseline and fails on new ones.
+With `update`, a baseline without new warnings is rewritten, so fixed warnings can't come back. */
+pub fn check(
+    diagnostics: &[Diagnostic],
+    baseline_path: &Path,
+    project_path: &Path,
+    update: bool,
+) -> Result<(), UnrealError> {
+    let root = repository::repository_root(project_path);
+    let current = count_warnings(diagnostics, root.as_deref());
+
+    if !baseline_path.exists() {
+        if !update {
+            return Err(UnrealError::InvalidBaseline {
+                reason: format!("{} doesn't exist", baseline_path.display()),
+            });
+        }
+
+        write_baseline(baseline_path, &current)?;
+        println!("Created the warning baseline : {}", baseline_path.display());
+        return Ok(());
+    }
+
+    let baseline = read_baseline(baseline_path)?;
+
+    let new_warnings: Vec<_> = current
+        .iter()
+        .filter_map(|(key, count)| {
+            let allowed = baseline.get(key).copied().unwrap_or(0);
+            (*count > allowed).then(|| (key, count - allowed))
+        })
+        .collect();
+
+    if !new_warnings.is_empty() {
+        println!("\nNew warnings compared to the baseline :");
+
+        for (key, count) in &new_warnings {
+            println!("    {} [{}] {} (x{count})", key.file, key.code, key.message);
+        }
+
+        return Err(UnrealError::NewWarnings {
+            count: new_warnings.iter().map(|(_, count)| count).sum(),
+        });
+    }
+
+    let fixed: usize = baseline
+        .iter()
+        .map(|(key, count)| count.saturating_sub(current.get(key).copied().unwrap_or(0)))
+        .sum();
+
+    println!("No new warnings, {fixed} baseline warning(s) fixed.");
+
+    if update && fixed > 0 {
+        write_baseline(baseline_path, &current)?;
+        println!("Updated the warning baseline : {}", baseline_path.display());
+    }
+
+    Ok(())
+}
+
+fn count_warnings(diagnostics: &[Diagnostic], root: Option<&Path>) -> BTreeMap<WarningKey, usize> {
+    let mut warnings = BTreeMap::new();
+
+    for diagnostic in diagnostics {
+        if diagnostic.severity != Severity::Warning {
+            continue;
+        }
+
+        let file = match (&diagnostic.file, root) {
+            (Some(file), Some(root)) => repository::relative_path(root, file),
+            (Some(file), None) => file.replace('\\', "/"),
+            (None, _) => String::new(),
+        };
+
+        let key = WarningKey {
+            file,
+            code: diagnostic.code.clone().unwrap_or_default(),
+            message: normalize_message(&diagnostic.message),
+        };
+
+        *warnings.entry(key).or_insert(0) += 1;
+    }
+
+    warnings
+}
+
+/* Collapses whitespace and masks numbers, which tend to be sizes, counts or line references. */
+fn normalize_message(message: &str) -> String {
+    let mut normalized = String::with_capacity(message.len());
+    let mut previous = ' ';
+
+    for c in message.trim().chars() {
+        let c = match c {
+            c if c.is_ascii_digit() => '#',
+            c if c.is_whitespace() => ' ',
+            c => c,
+        };
+
+        if (c == '#' || c == ' ') && c == previous {
+            continue;
+        }
+
+        normalized.push(c);
+        previous = c;
+    }
+
+    normalized
+}
+
+fn read_baseline(path: &Path) -> Result<BTreeMap<WarningKey, usize>, UnrealError> {
+    let contents = std::fs::read(path)?;
+
+    let baseline: WarningBaseline =
+        serde_json::from_slice(&contents).map_err(|err| UnrealError::InvalidBaseline {
+            reason: err.to_string(),
+        })?;
+
+    if baseline.version != BASELINE_VERSION {
+        return Err(UnrealError::InvalidBaseline {
+            reason: format!("unsupported version {}", baseline.version),
+        });
+    }
+
+    Ok(baseline
+        .warnings
+        .into_iter()
+        .map(|entry| {
+            let key = WarningKey {
+                file: entry.file,
+                code: entry.code,
+                message: entry.message,
+            };
+
+            (key, entry.count)
+        })
+        .collect())
+}
+
+fn write_baseline(path: &Path, warnings: &BTreeMap<WarningKey, usize>) -> Result<(), UnrealError> {
+    let baseline = WarningBaseline {
+        version: BASELINE_VERSION,
+        warnings: warnings
+            .iter()
+            .map(|(key, count)| BaselineEntry {
+                file: key.file.clone(),
+                code: key.code.clone(),
+                message: key.message.clone(),
+                count: *count,
+            })
+            .collect(),
+    };
+
+    let file = std::fs::File::create(path)?;
+    serde_json::to_writer_pretty(file, &baseline)?;
+
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    unreal_engine::{
+        build_matrix,
+        command::{self, UnrealCommand},
+        engine_association::EngineAssociation,
+        error::UnrealError,
+        unreal_installation::UnrealInstallation,
+        unreal_project::UnrealProject,
+    },
+    utility::{
+        path_utility, repository,
+        search::{self, SearchOptions},
+    },
+};
+
+/// The directories never searched for projects, they hold build products and plugins, not projects.
+pub const SKIPPED_DIRS: &[&str] = &["Intermediate", "Saved", "Plugins"];
+
+/// The engines resolved so far, so the projects using the same engine only look it up once.
+#[derive(Default)]
+pub struct EngineLookup {
+    engines: HashMap<String, UnrealInstallation>,
+}
+
+struct ProjectResult {
+    name: String,
+    engine: Option<String>,
+    passed: bool,
+    duration: Duration,
+}
+
+impl EngineLookup {
+    /* Resolves the engine of a project, reusing the one resolved for the same association or override.
+    An in-tree engine depends on where the project lives, so it is never shared. */
+    pub fn resolve(
+        &mut self,
+        association: &EngineAssociation,
+        uproject_path: &Path,
+        options: &SearchOptions,
+    ) -> Result<UnrealInstallation, UnrealError> {
+        let key = match (options.version_override(), association) {
+            (Some(requirement), _) => Some(format!("override {requirement}")),
+            (None, EngineAssociation::InTree) => None,
+            (None, association) => Some(format!("association {association}")),
+        };
+
+        if let Some(installation) = key.as_ref().and_then(|key| self.engines.get(key)) {
+            return Ok(installation.clone());
+        }
+
+        let installation =
+            UnrealInstallation::from_association(association, uproject_path, options)?;
+        println!(
+            "Engine : {} : {}",
+            installation.version, installation.base_path
+        );
+
+        if let Some(key) = key {
+            self.engines.insert(key, installation.clone());
+        }
+
+        Ok(installation)
+    }
+}
+
+/* Runs the command for every project of the workspace, one after the other, then prints a summary.
+A failing project doesn't stop the others. */
+pub fn run(command: &UnrealCommand, options: &SearchOptions) -> Result<(), UnrealError> {
+    let projects = find_projects(options)?;
+    let mut engines = EngineLookup::default();
+    let mut results = Vec::new();
+
+    for uproject_path in &projects {
+        let name = uproject_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        println!("\n==== {name} ====");
+
+        let start = Instant::now();
+        let mut engine = None;
+
+        let result =
+            UnrealProject::load(uproject_path, options, &mut engines).and_then(|project| {
+                engine = Some(project.engine().version.to_string());
+                let command = for_project(command, project.project_dir(), &name);
+                command::run_project_command(project, command)
+            });
+
+        if let Err(err) = &result {
+            eprintln!("{name} : {err}");
+        }
+
+        results.push(ProjectResult {
+            name,
+            engine,
+            passed: result.is_ok(),
+            duration: start.elapsed(),
+        });
+    }
+
+    print_summary(&results);
+
+    match results.iter().filter(|result| !result.passed).count() {
+        0 => Ok(()),
+        failures => Err(UnrealError::WorkspaceFailed {
+            failures,
+            total: results.len(),
+        }),
+    }
+}
+
+/* Every .uproject under the repository root, or the project directory outside of a repository.
+With --projects only the named ones, in the requested order. */
+pub fn find_projects(options: &SearchOptions) -> Result<Vec<PathBuf>, UnrealError> {
+    let start = path_utility::absolute_dir(&options.project_directory)
+        .ok_or(UnrealError::ProjectNotFound)?;
+
+    let root = repository::repository_root(&start).unwrap_or(start);
+    let projects = search::files_below(&root, "uproject", options.max_depth, SKIPPED_DIRS);
+
+    if options.projects.is_empty() {
+        return match projects.is_empty() {
+            true => Err(UnrealError::ProjectNotFound),
+            false => Ok(projects),
+        };
+    }
+
+    options
+        .projects
+        .iter()
+        .map(|name| {
+            let mut matching: Vec<&PathBuf> = projects
+                .iter()
+                .filter(|path| {
+                    path.file_stem()
+                        .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(name))
+                })
+                .collect();
+
+            match matching.len() {
+                0 => Err(UnrealError::NamedProjectNotFound { name: name.clone() }),
+                1 => Ok(matching.remove(0).clone()),
+                _ => Err(UnrealError::AmbiguousProject {
+                    candidates: matching
+                        .iter()
+                        .map(|path| path.to_string_lossy().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                }),
+            }
+        })
+        .collect()
+}
+
+/* Gives the command's output and baseline paths to the project, so the projects don't overwrite or compare against
+each other's. `{project}` is replaced with the project's name and relative paths are resolved in its directory.
+The inputs every project shares, like the Python script or the quarantine, are kept. */
+fn for_project(command: &UnrealCommand, project_dir: &Path, name: &str) -> UnrealCommand {
+    let local = |path: &mut String| {
+        let named = path.replace("{project}", name);
+
+        *path = match Path::new(&named).is_relative() {
+            true => project_dir.join(named).to_string_lossy().to_string(),
+            false => named,
+        };
+    };
+
+    let mut command = command.clone();
+
+    match &mut command {
+        UnrealCommand::Build(args) | UnrealCommand::BuildAndRun(args) => {
+            args.diagnostics_path.iter_mut().for_each(local);
+            args.warning_baseline.iter_mut().for_each(local);
+            local(&mut args.annotations_path);
+        }
+        UnrealCommand::Test(args) => {
+            local(&mut args.junit);
+            args.report_dir.iter_mut().for_each(local);
+            args.test_list.iter_mut().for_each(local);
+            args.timings.iter_mut().for_each(local);
+        }
+        UnrealCommand::Package(args) => {
+            args.archive_dir.iter_mut().for_each(local);
+            args.staging_dir.iter_mut().for_each(local);
+        }
+        UnrealCommand::Generate(args) => args.output_dir.iter_mut().for_each(local),
+        UnrealCommand::Commandlet(args) => args.json.iter_mut().for_each(local),
+        UnrealCommand::Python(args) => args.log.iter_mut().for_each(local),
+        UnrealCommand::ValidateAssets(args) => {
+            args.json.iter_mut().for_each(local);
+            args.junit.iter_mut().for_each(local);
+        }
+        _ => {}
+    }
+
+    command
+}
+
+fn print_summary(results: &[ProjectResult]) {
+    println!(
+        "\n{:<32} {:<10} {:<8} {:>10}",
+        "Project", "Engine", "Result", "Duration"
+    );
+
+    for result in results {
+        println!(
+            "{:<32} {:<10} {:<8} {:>10}",
+            result.name,
+            result.engine.as_deref().unwrap_or("-"),
+            match result.passed {
+                true => "Passed",
+                false => "Failed",
+            },
+            build_matrix::format_duration(result.duration)
+        );
+    }
+}
@@ -0,0 +1,10 @@
+/* FNV-1a over the parts, stable across runs, toolchains and machines unlike the std hasher. */
+pub fn fnv1a(parts: &[&str]) -> u64 {
+    parts.iter().fold(0xcbf29ce484222325u64, |hash, part| {
+        part.bytes()
+            .chain(std::iter::once(0))
+            .fold(hash, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            })
+    })
+}
@@ -0,0 +1,510 @@
+use std::{fmt::Write, io, path::Path};
+
+use quick_xml::{
+    Reader,
+    escape::resolve_predefined_entity,
+    events::{BytesStart, Event},
+};
+
+/// A JUnit test case, `classname` is the dotted path up to the test's own name.
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub classname: String,
+    pub name: String,
+    pub duration: f64,
+    pub outcome: TestOutcome,
+    /// warnings and other output, written to `system-out`.
+    pub output: Vec<String>,
+    /// the errors of the failed attempts of a test that passed on retry, one entry per attempt.
+    pub flaky_failures: Vec<String>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum TestOutcome {
+    Passed,
+    Failed { errors: Vec<String> },
+    Skipped { reason: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct TestSuite {
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+impl TestCase {
+    /* Splits a dotted test path into the classname and the test name. */
+    pub fn from_path(path: &str, duration: f64, outcome: TestOutcome) -> TestCase {
+        let (classname, name) = path.rsplit_once('.').unwrap_or(("", path));
+
+        TestCase {
+            classname: classname.to_owned(),
+            name: name.to_owned(),
+            duration,
+            outcome,
+            output: Vec::new(),
+            flaky_failures: Vec::new(),
+        }
+    }
+
+    pub fn full_name(&self) -> String {
+        match self.classname.is_empty() {
+            true => self.name.clone(),
+            false => format!("{}.{}", self.classname, self.name),
+        }
+    }
+}
+
+impl TestSuite {
+    pub fn failures(&self) -> usize {
+        self.count(|outcome| matches!(outcome, TestOutcome::Failed { .. }))
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.count(|outcome| matches!(outcome, TestOutcome::Skipped { .. }))
+    }
+
+    fn count(&self, predicate: impl Fn(&TestOutcome) -> bool) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| predicate(&case.outcome))
+            .count()
+    }
+
+    pub fn duration(&self) -> f64 {
+        // Summing floats starts from -0.0, which an empty report would print.
+        self.cases
+            .iter()
+            .fold(0.0, |total, case| total + case.duration)
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+        let _ = writeln!(
+            xml,
+            "<testsuites tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            self.cases.len(),
+            self.failures(),
+            self.skipped(),
+            self.duration()
+        );
+
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            escape(&self.name),
+            self.cases.len(),
+            self.failures(),
+            self.skipped(),
+            self.duration()
+        );
+
+        for case in &self.cases {
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\">",
+                escape(&case.classname),
+                escape(&case.name),
+                case.duration
+            );
+
+            match &case.outcome {
+                TestOutcome::Passed => {}
+                TestOutcome::Failed { errors } => {
+                    let message = errors.first().map(String::as_str).unwrap_or("failed");
+
+                    let _ = write!(
+                        xml,
+                        "\n      <failure message=\"{}\">{}</failure>",
+                        escape(message),
+                        escape(&errors.join("\n"))
+                    );
+                }
+                TestOutcome::Skipped { reason } => {
+                    let _ = write!(xml, "\n      <skipped message=\"{}\"/>", escape(reason));
+                }
+            }
+
+            // The surefire extension most CI servers understand for tests that passed on retry.
+            for failure in &case.flaky_failures {
+                let message = failure.lines().next().unwrap_or("failed");
+
+                let _ = write!(
+                    xml,
+                    "\n      <flakyFailure message=\"{}\">{}</flakyFailure>",
+                    escape(message),
+                    escape(failure)
+                );
+            }
+
+            if !case.output.is_empty() {
+                let _ = write!(
+                    xml,
+                    "\n      <system-out>{}</system-out>",
+                    escape(&case.output.join("\n"))
+                );
+            }
+
+            let closing = match (
+                &case.outcome,
+                case.output.is_empty() && case.flaky_failures.is_empty(),
+            ) {
+                (TestOutcome::Passed, true) => "</testcase>\n",
+                _ => "\n    </testcase>\n",
+            };
+
+            xml.push_str(closing);
+        }
+
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, self.to_xml())
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab and newlines aren't allowed in XML 1.0.
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// The element whose text is being read.
+#[derive(PartialEq)]
+enum Capture {
+    None,
+    Failure,
+    FlakyFailure,
+    Output,
+}
+
+/* Reads every test case of a JUnit report, across all of its suites. */
+pub fn read_cases(path: &Path) -> io::Result<Vec<TestCase>> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut reader = Reader::from_str(&contents);
+
+    let mut cases = Vec::new();
+    let mut current: Option<TestCase> = None;
+    let mut capture = Capture::None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event().map_err(io::Error::other)? {
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"testcase" => current = Some(read_case(&element)),
+                b"failure" | b"error" => {
+                    set_failure(current.as_mut(), &element);
+                    capture = Capture::Failure;
+                    text.clear();
+                }
+                b"skipped" => set_skipped(current.as_mut(), &element),
+                b"flakyFailure" => {
+                    capture = Capture::FlakyFailure;
+                    text.clear();
+                }
+                b"system-out" => {
+                    capture = Capture::Output;
+                    text.clear();
+                }
+                _ => {}
+            },
+            // An empty element has no End event.
+            Event::Empty(element) => match element.local_name().as_ref() {
+                b"testcase" => cases.push(read_case(&element)),
+                b"failure" | b"error" => set_failure(current.as_mut(), &element),
+                b"skipped" => set_skipped(current.as_mut(), &element),
+                b"flakyFailure" => {
+                    if let Some(case) = current.as_mut() {
+                        case.flaky_failures.push(attribute(&element, "message"));
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(content) if capture != Capture::None => {
+                text.push_str(&content.decode().map_err(io::Error::other)?);
+            }
+            Event::CData(content) if capture != Capture::None => {
+                text.push_str(&String::from_utf8_lossy(&content));
+            }
+            Event::GeneralRef(reference) if capture != Capture::None => {
+                let resolved = match reference.resolve_char_ref().map_err(io::Error::other)? {
+                    Some(c) => Some(c.to_string()),
+                    None => {
+                        let name = reference.decode().map_err(io::Error::other)?;
+                        resolve_predefined_entity(&name).map(str::to_owned)
+                    }
+                };
+
+                text.push_str(&resolved.unwrap_or_default());
+            }
+            Event::End(element) => match element.local_name().as_ref() {
+                b"testcase" => cases.extend(current.take()),
+                b"failure" | b"error" => {
+                    if let Some(TestOutcome::Failed { errors }) =
+                        current.as_mut().map(|case| &mut case.outcome)
+                        && !text.trim().is_empty()
+                    {
+                        *errors = text.lines().map(str::to_owned).collect();
+                    }
+
+                    capture = Capture::None;
+                }
+                b"flakyFailure" => {
+                    if let Some(case) = current.as_mut() {
+                        case.flaky_failures.push(text.clone());
+                    }
+
+                    capture = Capture::None;
+                }
+                b"system-out" => {
+                    if let Some(case) = current.as_mut() {
+                        case.output = text.lines().map(str::to_owned).collect();
+                    }
+
+                    capture = Capture::None;
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(cases)
+}
+
+fn read_case(element: &BytesStart) -> TestCase {
+    let classname = attribute(element, "classname");
+    let name = attribute(element, "name");
+
+    TestCase {
+        classname,
+        name,
+        duration: attribute(element, "time").parse().unwrap_or(0.0),
+        outcome: TestOutcome::Passed,
+        output: Vec::new(),
+        flaky_failures: Vec::new(),
+    }
+}
+
+fn set_failure(case: Option<&mut TestCase>, element: &BytesStart) {
+    if let Some(case) = case {
+        let message = attribute(element, "message");
+
+        case.outcome = TestOutcome::Failed {
+            errors: (!message.is_empty())
+                .then_some(message)
+                .into_iter()
+                .collect(),
+        };
+    }
+}
+
+fn set_skipped(case: Option<&mut TestCase>, element: &BytesStart) {
+    if let Some(case) = case {
+        case.outcome = TestOutcome::Skipped {
+            reason: attribute(element, "message"),
+        };
+    }
+}
+
+fn attribute(element: &BytesStart, name: &str) -> String {
+    element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|attribute| attribute.unescape_value().ok())
+        .map(|value| value.into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_test_paths() {
+        let cases = [
+            ("Project.Gameplay.Jump", "Project.Gameplay", "Jump"),
+            ("Jump", "", "Jump"),
+        ];
+
+        for (path, classname, name) in cases {
+            let case = TestCase::from_path(path, 0.0, TestOutcome::Passed);
+
+            assert_eq!(
+                (case.classname.as_str(), case.name.as_str()),
+                (classname, name),
+                "{path}"
+            );
+            assert_eq!(case.full_name(), path);
+        }
+    }
+
+    #[test]
+    fn writes_counts_and_outcomes() {
+        let mut warned = TestCase::from_path("Project.Warned", 0.25, TestOutcome::Passed);
+        warned.output = vec![String::from("a <b> & \"c\"")];
+
+        let suite = TestSuite {
+            name: String::from("Unreal\u{1}Automation"),
+            cases: vec![
+                TestCase::from_path("Project.Passed", 1.5, TestOutcome::Passed),
+                TestCase::from_path(
+                    "Project.Failed",
+                    2.0,
+                    TestOutcome::Failed {
+                        errors: vec![String::from("first"), String::from("second")],
+                    },
+                ),
+                TestCase::from_path(
+                    "Project.Skipped",
+                    0.0,
+                    TestOutcome::Skipped {
+                        reason: String::from("disabled"),
+                    },
+                ),
+                warned,
+            ],
+        };
+
+        let xml = suite.to_xml();
+
+        for expected in [
+            "<testsuites tests=\"4\" failures=\"1\" skipped=\"1\" time=\"3.750\">",
+            "<testsuite name=\"UnrealAutomation\" tests=\"4\"",
+            "<testcase classname=\"Project\" name=\"Passed\" time=\"1.500\"></testcase>",
+            "<failure message=\"first\">first\nsecond</failure>",
+            "<skipped message=\"disabled\"/>",
+            "<system-out>a &lt;b&gt; &amp; &quot;c&quot;</system-out>",
+        ] {
+            assert!(xml.contains(expected), "{expected}\n{xml}");
+        }
+    }
+
+    #[test]
+    fn reads_back_written_reports() {
+        let mut flaky = TestCase::from_path("Project.Flaky", 3.0, TestOutcome::Passed);
+        flaky.flaky_failures = vec![String::from("timed out\nafter 30s")];
+        flaky.output = vec![String::from("warning <one>"), String::from("warning two")];
+
+        let suite = TestSuite {
+            name: String::from("UnrealAutomation"),
+            cases: vec![
+                TestCase::from_path(
+                    "Project.Failed",
+                    2.0,
+                    TestOutcome::Failed {
+                        errors: vec![String::from("expected 1 & got 2"), String::from("second")],
+                    },
+                ),
+                TestCase::from_path(
+                    "Project.Skipped",
+                    0.0,
+                    TestOutcome::Skipped {
+                        reason: String::from("disabled"),
+                    },
+                ),
+                flaky,
+            ],
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.xml");
+        suite.write(&path).unwrap();
+
+        let cases = read_cases(&path).unwrap();
+
+        assert_eq!(cases.len(), suite.cases.len());
+
+        for (read, written) in cases.iter().zip(&suite.cases) {
+            assert_eq!(read.full_name(), written.full_name());
+            assert_eq!(read.duration, written.duration, "{}", written.name);
+            assert_eq!(read.outcome, written.outcome, "{}", written.name);
+            assert_eq!(read.output, written.output, "{}", written.name);
+            assert_eq!(
+                read.flaky_failures, written.flaky_failures,
+                "{}",
+                written.name
+            );
+        }
+    }
+
+    #[test]
+    fn reads_reports_from_other_tools() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="First">
+    <testcase classname="Project.A" name="Passed" time="0.5"/>
+    <testcase classname="Project.A" name="Errored"><error message="crashed"/></testcase>
+  </testsuite>
+  <testsuite name="Second">
+    <testcase classname="Project.B" name="Failed"><failure><![CDATA[line <1>]]>&#10;line 2</failure></testcase>
+    <testcase classname="Project.B" name="Skipped"><skipped/></testcase>
+  </testsuite>
+</testsuites>
+"#;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.xml");
+        std::fs::write(&path, xml).unwrap();
+
+        let failed = |errors: &[&str]| TestOutcome::Failed {
+            errors: errors.iter().map(|error| error.to_string()).collect(),
+        };
+
+        let expected = [
+            ("Project.A.Passed", TestOutcome::Passed),
+            ("Project.A.Errored", failed(&["crashed"])),
+            ("Project.B.Failed", failed(&["line <1>", "line 2"])),
+            (
+                "Project.B.Skipped",
+                TestOutcome::Skipped {
+                    reason: String::new(),
+                },
+            ),
+        ];
+
+        let cases = read_cases(&path).unwrap();
+
+        assert_eq!(cases.len(), expected.len());
+
+        for (case, (name, outcome)) in cases.iter().zip(expected) {
+            assert_eq!(case.full_name(), name);
+            assert_eq!(case.outcome, outcome, "{name}");
+        }
+    }
+
+    #[test]
+    fn empty_suites_have_no_negative_duration() {
+        let suite = TestSuite {
+            name: String::from("UnrealAutomation"),
+            cases: Vec::new(),
+        };
+
+        assert!(suite.to_xml().contains("time=\"0.000\""));
+    }
+}
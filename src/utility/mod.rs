@@ -1,2 +1,5 @@
+pub mod hash;
+pub mod junit;
 pub mod path_utility;
+pub mod repository;
 pub mod search;
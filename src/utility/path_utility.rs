@@ -1,4 +1,4 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 pub fn remove_extension(path: impl AsRef<Path>) -> PathBuf {
     path.as_ref().with_extension("")
@@ -10,3 +10,71 @@ pub fn filename_as_string(path: impl AsRef<Path>) -> Option<String> {
         .and_then(|name| name.to_str())
         .map(|name| name.to_owned())
 }
+
+/* Joins the path to the base and folds its `.` and `..` components, without touching the file system. */
+pub fn join_normalized(base: impl AsRef<Path>, path: impl AsRef<Path>) -> PathBuf {
+    let mut joined = PathBuf::new();
+
+    for component in base.as_ref().join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                joined.pop();
+            }
+            component => joined.push(component),
+        }
+    }
+
+    joined
+}
+
+/* Drops the `\\?\` prefix Windows' canonicalize adds, tools like UBT only understand the plain `C:\` form. */
+pub fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let text = path.to_string_lossy();
+
+    if let Some(share) = text.strip_prefix(r"\\?\UNC\") {
+        return PathBuf::from(format!(r"\\{share}"));
+    }
+
+    match text.strip_prefix(r"\\?\") {
+        Some(plain) => PathBuf::from(plain),
+        None => path,
+    }
+}
+
+/* The path relative to the base, both absolute and normalized, Ex : `../Source/Main.cpp`.
+None when they don't share a root, like two Windows drives. */
+pub fn relative_to(base: impl AsRef<Path>, path: impl AsRef<Path>) -> Option<PathBuf> {
+    let base: Vec<Component> = base.as_ref().components().collect();
+    let path: Vec<Component> = path.as_ref().components().collect();
+
+    let common = base
+        .iter()
+        .zip(&path)
+        .take_while(|(base, path)| base == path)
+        .count();
+
+    if common == 0 {
+        return None;
+    }
+
+    let mut relative: PathBuf = base[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .collect();
+    relative.extend(&path[common..]);
+
+    match relative.as_os_str().is_empty() {
+        true => Some(PathBuf::from(".")),
+        false => Some(relative),
+    }
+}
+
+/* The directory as an absolute path, without resolving its symlinks or adding `\\?\` like canonicalize does.
+None when it isn't a directory. */
+pub fn absolute_dir(path: impl AsRef<Path>) -> Option<PathBuf> {
+    let absolute = std::path::absolute(path).ok()?;
+    let normalized = join_normalized(&absolute, "");
+
+    normalized.is_dir().then_some(normalized)
+}
@@ -0,0 +1,62 @@
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+
+use crate::utility::path_utility;
+
+/* Returns the working directory of the git repository containing the path, if any. */
+pub fn repository_root(path: impl AsRef<Path>) -> Option<PathBuf> {
+    let repository = Repository::discover(path).ok()?;
+    let workdir = repository.workdir()?;
+
+    Some(path_utility::strip_verbatim(
+        workdir
+            .canonicalize()
+            .unwrap_or_else(|_| workdir.to_path_buf()),
+    ))
+}
+
+/* Makes the path relative to the root with forward slashes, leaving it untouched when it lies outside the root. */
+pub fn relative_path(root: &Path, path: impl AsRef<Path>) -> String {
+    let path = path.as_ref();
+    let absolute =
+        path_utility::strip_verbatim(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+
+    let relative = absolute
+        .strip_prefix(root)
+        .or_else(|_| path.strip_prefix(root))
+        .unwrap_or(path);
+
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/* The files changed since the merge base of HEAD and the base revision, committed, staged or not, as absolute paths. */
+pub fn changed_files(path: impl AsRef<Path>, base: &str) -> Result<Vec<PathBuf>, git2::Error> {
+    let repository = Repository::discover(path)?;
+    let workdir = repository
+        .workdir()
+        .ok_or_else(|| git2::Error::from_str("the repository has no working directory"))?;
+    let workdir = path_utility::strip_verbatim(
+        workdir
+            .canonicalize()
+            .unwrap_or_else(|_| workdir.to_path_buf()),
+    );
+
+    let base = repository.revparse_single(base)?.peel_to_commit()?.id();
+    let head = repository.head()?.peel_to_commit()?.id();
+
+    let merge_base = repository.merge_base(base, head)?;
+    let tree = repository.find_commit(merge_base)?.tree()?;
+
+    let mut options = git2::DiffOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+
+    let diff = repository.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))?;
+
+    // Deleted files have no new path worth validating.
+    Ok(diff
+        .deltas()
+        .filter(|delta| delta.status() != git2::Delta::Deleted)
+        .filter_map(|delta| delta.new_file().path().map(|path| workdir.join(path)))
+        .collect())
+}
@@ -1,8 +1,11 @@
 use std::path::{Path, PathBuf};
 
-use clap::{Parser, command};
+use clap::Parser;
+use ignore::WalkBuilder;
 use serde::Serialize;
 
+use crate::unreal_engine::version_requirement::VersionRequirement;
+
 #[derive(Parser, Clone, Debug, Serialize)]
 #[command(version, about, long_about = None)]
 /// options used for directory searches.
@@ -15,21 +18,59 @@ pub struct SearchOptions {
     /// the directory of the uproject file, without the filename. Ex : C:\\ProjectName
     pub project_directory: String,
 
-    #[arg(short = None, long = "major", default_value_t = 5)]
-    /// the unreal engine major version to target.
-    pub ue_major_version: u16,
+    #[arg(long = "project")]
+    /// picks the project by name when several are found. Ex : ProjectName
+    pub project_name: Option<String>,
+
+    #[arg(long, conflicts_with_all = ["projects", "project_name"])]
+    /// runs the command for every project under the repository root. Relative output paths are resolved in each
+    /// project's directory and {project} in them is replaced with its name.
+    pub all: bool,
+
+    #[arg(long, value_delimiter = ',', conflicts_with = "project_name")]
+    /// runs the command for the named projects under the repository root. Ex : ProjectA,ProjectB
+    pub projects: Vec<String>,
+
+    #[arg(short = None, long = "major")]
+    /// overrides the unreal engine major version declared by the project.
+    pub ue_major_version: Option<u16>,
+
+    #[arg(short = None, long = "minor", requires = "ue_major_version")]
+    /// overrides the unreal engine minor version declared by the project.
+    pub ue_minor_version: Option<u16>,
 
-    #[arg(short = None, long = "minor", default_value_t = 6)]
-    /// the unreal engine minor version to target.
-    pub ue_minor_version: u16,
+    #[arg(short = None, long = "patch", requires = "ue_minor_version")]
+    /// overrides the unreal engine patch version declared by the project.
+    pub ue_patch_version: Option<u16>,
 
-    #[arg(short = None, long = "patch", default_value_t = 1)]
-    /// the unreal engine patch version to target.
-    pub ue_patch_version: u16,
+    #[arg(
+        short = 'e',
+        long = "engine-version",
+        conflicts_with = "ue_major_version"
+    )]
+    /// overrides the engine version with a requirement. Ex : ">=5.4, <5.7"
+    pub engine_version: Option<VersionRequirement>,
 
     #[arg(short = 's', long = "directory", num_args=1.., value_delimiter=' ', default_value = default_dir())]
     /// the directory to use as the UnrealEngine search root.
     pub search_dir: Vec<String>,
+
+    #[arg(long)]
+    /// ignores the machine's engine index and walks the search roots again.
+    pub rescan: bool,
+}
+
+impl SearchOptions {
+    /* The engine version requested on the command line, which takes precedence over the project's. */
+    pub fn version_override(&self) -> Option<VersionRequirement> {
+        if let Some(requirement) = &self.engine_version {
+            return Some(requirement.clone());
+        }
+
+        self.ue_major_version.map(|major| {
+            VersionRequirement::exact(major, self.ue_minor_version, self.ue_patch_version)
+        })
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -63,19 +104,55 @@ fn default_dir() -> String {
     String::from("/")
 }
 
-pub fn file_with_extension(search_dir: impl AsRef<Path>, target_ext: &str) -> Option<PathBuf> {
-    let entries = std::fs::read_dir(search_dir).ok()?;
+/* Every file with the extension directly in the directory, sorted so the result doesn't depend on `read_dir`'s order. */
+pub fn files_with_extension(search_dir: impl AsRef<Path>, target_ext: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(search_dir) else {
+        return Vec::new();
+    };
 
-    entries.into_iter().find_map(|result| {
-        let entry = result.ok()?;
-        let path = entry.path();
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|result| {
+            let path = result.ok()?.path();
+            let extension = path.extension()?.to_str()?;
 
-        let extension = path.extension()?.to_str()?;
+            (extension == target_ext && path.is_file()).then_some(path)
+        })
+        .collect();
 
-        if extension == target_ext {
-            return Some(path);
-        }
+    files.sort();
+    files
+}
+
+/* Every file with the extension below the directory, down to the depth and honoring .gitignore files.
+Directories with one of the skipped names aren't entered. */
+pub fn files_below(
+    search_dir: impl AsRef<Path>,
+    target_ext: &str,
+    max_depth: usize,
+    skipped_dirs: &[&str],
+) -> Vec<PathBuf> {
+    let skipped_dirs: Vec<String> = skipped_dirs.iter().map(|dir| dir.to_string()).collect();
+
+    let mut files: Vec<PathBuf> = WalkBuilder::new(search_dir)
+        .max_depth(Some(max_depth))
+        .follow_links(false)
+        .filter_entry(move |entry| {
+            let is_dir = entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_dir());
+            let name = entry.file_name().to_string_lossy();
+
+            entry.depth() == 0 || !is_dir || !skipped_dirs.iter().any(|dir| **dir == *name)
+        })
+        .build()
+        .filter_map(|result| {
+            let path = result.ok()?.into_path();
+            let extension = path.extension()?.to_str()?;
+
+            (extension == target_ext && path.is_file()).then_some(path)
+        })
+        .collect();
 
-        None
-    })
+    files.sort();
+    files
 }